@@ -0,0 +1,45 @@
+/***** Settings of the executor ******/
+use std::time::Duration;
+
+/// Settings given to JniCache::init_with_config.
+#[derive(Debug, Clone)]
+pub struct JniCacheConfig {
+    /// Number of local references reserved by the local frame every call runs in.
+    /// The frame is popped once the call is answered, so the local references created
+    /// by a call never outlive it. The objects kept in the JObjectStore are global refs.
+    pub local_frame_capacity: i32,
+    /// A method or field called by name without being registered is resolved through the
+    /// class loader on its first call, and cached for the next ones.
+    pub resolve_on_first_call: bool,
+    /// Deadline given to every call, counted from the moment it is sent. None waits for
+    /// the answer however long the calls queued ahead of it take.
+    pub call_timeout: Option<Duration>,
+    /// Number of executor threads, each one attached to the jvm with its own JNIEnv and
+    /// sharing the cache. A call on a stored object runs on the worker its id is bound to,
+    /// after the previous calls on that object ; any other call runs on the least loaded
+    /// worker, so calls sent without waiting for each other may run in any order.
+    pub pool_size: usize,
+    /// Named lanes, each one with a dedicated executor thread running its calls in the
+    /// order they are sent, so a burst of calls on a lane never delays the other lanes.
+    /// A call on a stored object that still has calls queued or running elsewhere follows
+    /// them instead, the calls on an object always run in the order they are sent.
+    pub lanes: Vec<String>,
+    /// Name of the executor threads, suffixed with their index in the pool or their lane.
+    pub thread_name: String,
+    /// Stack size of the executor threads, the std default when None.
+    pub stack_size: Option<usize>,
+}
+
+impl Default for JniCacheConfig {
+    fn default() -> Self {
+        Self {
+            local_frame_capacity: 32,
+            resolve_on_first_call: true,
+            call_timeout: None,
+            pool_size: 1,
+            lanes: Vec::new(),
+            thread_name: "jvm-executor".to_string(),
+            stack_size: None,
+        }
+    }
+}
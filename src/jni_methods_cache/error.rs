@@ -0,0 +1,159 @@
+/***** Errors returned by the cache and the executor ******/
+use std::fmt;
+
+use crate::jni_methods_cache::methods_cache::java_method_cache_utils::{
+    FieldHandle, MethodHandle, ObjectId,
+};
+
+#[derive(Debug)]
+pub enum JniCacheError {
+    /// No cached method matches the given class, name and signature.
+    MethodNotRegistered {
+        class: String,
+        name: String,
+        sig: String,
+    },
+    /// The handle does not designate a cached method of the requested kind.
+    InvalidMethodHandle(MethodHandle),
+    /// No cached field matches the given class, name and signature.
+    FieldNotRegistered {
+        class: String,
+        name: String,
+        sig: String,
+    },
+    /// The handle does not designate a cached field of the requested kind.
+    InvalidFieldHandle(FieldHandle),
+    /// A class, method or field registered on the live cache could not be resolved.
+    ResolutionFailed { member: String, reason: String },
+    /// The object id is not present in the JObjectStore.
+    ObjectNotFound(ObjectId),
+    /// The receiver object of an instance call is not an instance of the method class.
+    WrongReceiverClass { object_id: ObjectId, class: String },
+    /// The arguments do not match the arity or the types of the method signature.
+    ArgumentMismatch { sig: String, message: String },
+    /// An argument could not be converted to a jvalue.
+    ArgumentConversion(String),
+    /// The java method threw an exception, it has been cleared from the JNIEnv.
+    JavaException(JavaThrowable),
+    /// The cache has not been initialized or built yet.
+    CacheNotBuilt,
+    /// The build of the cache failed at init, its executor is stopped.
+    BuildFailed(String),
+    /// The executor thread is gone, the call could not be sent or answered.
+    ExecutorDisconnected,
+    /// The deadline of the call passed before it was answered, a late result is discarded.
+    Timeout,
+    /// The lane is not declared in the JniCacheConfig.
+    UnknownLane(String),
+    /// The value returned by java is not of the type requested by the caller.
+    ReturnTypeMismatch {
+        expected: &'static str,
+        found: String,
+    },
+    /// Any other error reported by the jni crate.
+    Jni(jni::errors::Error),
+}
+
+impl fmt::Display for JniCacheError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JniCacheError::MethodNotRegistered { class, name, sig } => {
+                write!(f, "method [{}.{}{}] is not registered", class, name, sig)
+            }
+            JniCacheError::InvalidMethodHandle(handle) => {
+                write!(f, "[{:?}] does not designate a cached method", handle)
+            }
+            JniCacheError::FieldNotRegistered { class, name, sig } => {
+                write!(f, "field [{}.{}: {}] is not registered", class, name, sig)
+            }
+            JniCacheError::InvalidFieldHandle(handle) => {
+                write!(f, "[{:?}] does not designate a cached field", handle)
+            }
+            JniCacheError::ResolutionFailed { member, reason } => {
+                write!(f, "cannot resolve [{}]: {}", member, reason)
+            }
+            JniCacheError::ObjectNotFound(id) => {
+                write!(f, "no object with id [{}] in the object store", id)
+            }
+            JniCacheError::WrongReceiverClass { object_id, class } => {
+                write!(
+                    f,
+                    "object [{}] is not an instance of [{}]",
+                    object_id, class
+                )
+            }
+            JniCacheError::ArgumentMismatch { sig, message } => {
+                write!(f, "arguments do not match [{}]: {}", sig, message)
+            }
+            JniCacheError::ArgumentConversion(msg) => {
+                write!(f, "argument conversion failed: {}", msg)
+            }
+            JniCacheError::JavaException(throwable) => {
+                write!(f, "java exception thrown: {}", throwable)
+            }
+            JniCacheError::CacheNotBuilt => write!(f, "java method cache is not built"),
+            JniCacheError::BuildFailed(reason) => {
+                write!(f, "java method cache build failed: {}", reason)
+            }
+            JniCacheError::ExecutorDisconnected => write!(f, "jvm executor is disconnected"),
+            JniCacheError::Timeout => write!(f, "the deadline of the call has passed"),
+            JniCacheError::UnknownLane(lane) => write!(f, "lane [{}] is not declared", lane),
+            JniCacheError::ReturnTypeMismatch { expected, found } => {
+                write!(
+                    f,
+                    "expected a [{}] return value, found [{}]",
+                    expected, found
+                )
+            }
+            JniCacheError::Jni(e) => write!(f, "jni error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for JniCacheError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            JniCacheError::JavaException(throwable) => Some(throwable),
+            JniCacheError::Jni(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<jni::errors::Error> for JniCacheError {
+    fn from(e: jni::errors::Error) -> Self {
+        JniCacheError::Jni(e)
+    }
+}
+
+/// Rust side copy of a java Throwable, with its cause chain.
+#[derive(Debug, Clone)]
+pub struct JavaThrowable {
+    pub class_name: String,
+    pub message: Option<String>,
+    pub stack_trace: Vec<String>,
+    pub cause: Option<Box<JavaThrowable>>,
+}
+
+impl JavaThrowable {
+    pub fn causes(&self) -> impl Iterator<Item = &JavaThrowable> {
+        std::iter::successors(self.cause.as_deref(), |t| t.cause.as_deref())
+    }
+}
+
+impl fmt::Display for JavaThrowable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.message {
+            Some(message) => write!(f, "{}: {}", self.class_name, message),
+            None => write!(f, "{}", self.class_name),
+        }
+    }
+}
+
+impl std::error::Error for JavaThrowable {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.cause
+            .as_deref()
+            .map(|cause| cause as &(dyn std::error::Error + 'static))
+    }
+}
@@ -1,68 +1,332 @@
-use crate::jni_methods_cache::methods_cache::{JVMResponse, JVMResult, JVMResultSender};
+use crate::jni_methods_cache::error::JniCacheError;
+use crate::jni_methods_cache::methods_cache::{
+    JVMAsyncResult, JVMResponse, JVMResult, JVMResultSender,
+};
 pub use channel::ExecutorChannel;
 pub use event_handler::run;
 pub use executor_receiver::ExecutorReceiver;
-use jvm_call_event::*;
+pub(crate) use jvm_call_event::{JvmCallEvent, Registered, Registration};
 pub use jvm_caller::JvmCaller;
 
 mod jvm_call_event {
     use std::time::Instant;
 
     use super::*;
-    use crate::{JavaArgs, ReturnType};
+    use crate::{
+        FieldHandle, FieldType, JavaArgs, JavaFieldRef, JavaMethodRef, MethodHandle, MethodType,
+        ObjectId,
+    };
+
+    /// What is added to the live cache by a Register event.
+    #[derive(Debug)]
+    pub enum Registration {
+        StandardClass(String),
+        Method {
+            method_type: MethodType,
+            class: String,
+            name: String,
+            sig: String,
+        },
+        Field {
+            field_type: FieldType,
+            class: String,
+            name: String,
+            sig: String,
+        },
+    }
+
+    #[derive(Debug)]
+    pub enum Registered {
+        StandardClass,
+        Method(MethodHandle),
+        Field(FieldHandle),
+    }
+
+    pub type RegisteredSender = kanal::Sender<Result<Registered, JniCacheError>>;
 
     #[derive(Debug)]
     pub enum JvmCallEvent {
         CallStaticMethod {
             response_channel: JVMResultSender,
-            class_name: String,
-            method_name: String,
-            sig: String,
+            method: JavaMethodRef,
+            args: JavaArgs,
+            returned_object_id: Option<ObjectId>,
+            instant: Instant,
+            deadline: Option<Instant>,
+        },
+        CallMethod {
+            response_channel: JVMResultSender,
+            receiver_id: ObjectId,
+            method: JavaMethodRef,
             args: JavaArgs,
-            return_type: ReturnType,
-            returned_object_id: Option<String>,
+            returned_object_id: Option<ObjectId>,
             instant: Instant,
+            deadline: Option<Instant>,
+        },
+        NewObject {
+            response_channel: JVMResultSender,
+            constructor: JavaMethodRef,
+            args: JavaArgs,
+            returned_object_id: Option<ObjectId>,
+            instant: Instant,
+            deadline: Option<Instant>,
+        },
+        /// Reads a static field when receiver_id is None.
+        GetField {
+            response_channel: JVMResultSender,
+            receiver_id: Option<ObjectId>,
+            field: JavaFieldRef,
+            returned_object_id: Option<ObjectId>,
+            instant: Instant,
+            deadline: Option<Instant>,
+        },
+        /// Writes a static field when receiver_id is None.
+        SetField {
+            response_channel: JVMResultSender,
+            receiver_id: Option<ObjectId>,
+            field: JavaFieldRef,
+            value: JavaArgs,
+            instant: Instant,
+            deadline: Option<Instant>,
+        },
+        Register {
+            response_channel: RegisteredSender,
+            registration: Registration,
+            deadline: Option<Instant>,
         },
-        CallMethod,
+    }
+
+    impl JvmCallEvent {
+        pub fn deadline(&self) -> Option<Instant> {
+            match self {
+                JvmCallEvent::CallStaticMethod { deadline, .. }
+                | JvmCallEvent::CallMethod { deadline, .. }
+                | JvmCallEvent::NewObject { deadline, .. }
+                | JvmCallEvent::GetField { deadline, .. }
+                | JvmCallEvent::SetField { deadline, .. }
+                | JvmCallEvent::Register { deadline, .. } => *deadline,
+            }
+        }
+
+        /// The stored object the call runs on, its calls are kept in order.
+        pub fn receiver_id(&self) -> Option<&ObjectId> {
+            match self {
+                JvmCallEvent::CallMethod { receiver_id, .. } => Some(receiver_id),
+                JvmCallEvent::GetField { receiver_id, .. }
+                | JvmCallEvent::SetField { receiver_id, .. } => receiver_id.as_ref(),
+                _ => None,
+            }
+        }
+
+        pub fn is_expired(&self) -> bool {
+            self.deadline()
+                .is_some_and(|deadline| Instant::now() >= deadline)
+        }
+
+        /// Drops the call from the queue without running it, its caller gets Timeout.
+        pub fn expire(self) {
+            let (response_channel, member, instant) = match self {
+                JvmCallEvent::CallStaticMethod {
+                    response_channel,
+                    method,
+                    instant,
+                    ..
+                }
+                | JvmCallEvent::CallMethod {
+                    response_channel,
+                    method,
+                    instant,
+                    ..
+                } => (response_channel, method.method_name(), instant),
+                JvmCallEvent::NewObject {
+                    response_channel,
+                    constructor,
+                    instant,
+                    ..
+                } => (response_channel, constructor.method_name(), instant),
+                JvmCallEvent::GetField {
+                    response_channel,
+                    field,
+                    instant,
+                    ..
+                }
+                | JvmCallEvent::SetField {
+                    response_channel,
+                    field,
+                    instant,
+                    ..
+                } => (response_channel, field.field_name(), instant),
+                JvmCallEvent::Register {
+                    response_channel, ..
+                } => {
+                    let _ = response_channel.send(Err(JniCacheError::Timeout));
+                    return;
+                }
+            };
+            log::warn!(
+                "call of [{}] dropped after {:?} in the queue, its deadline has passed",
+                member,
+                instant.elapsed()
+            );
+            let _ = response_channel.send(Err(JniCacheError::Timeout));
+        }
     }
 }
 mod channel {
+    use std::time::Duration;
+
     use super::*;
+    use crate::jni_methods_cache::thread_pool::{
+        Dispatcher, ObjectRoutes, WorkerLoad, WorkerQueue,
+    };
+
+    /// Queue of a worker with the number of calls it has to run, named after its index in
+    /// the pool or after its lane.
+    struct WorkerChannel {
+        name: String,
+        sender: kanal::Sender<JvmCallEvent>,
+        receiver: kanal::Receiver<JvmCallEvent>,
+        load: WorkerLoad,
+    }
+
+    impl WorkerChannel {
+        fn new(name: String) -> Self {
+            let (sender, receiver) = kanal::unbounded();
+            WorkerChannel {
+                name,
+                sender,
+                receiver,
+                load: WorkerLoad::default(),
+            }
+        }
+
+        fn queue(&self) -> WorkerQueue {
+            WorkerQueue::new(self.sender.clone(), self.load.clone())
+        }
+
+        fn receiver(&self, routes: &ObjectRoutes) -> (String, ExecutorReceiver) {
+            (
+                self.name.clone(),
+                ExecutorReceiver::new(self.receiver.clone(), self.load.clone(), routes.clone()),
+            )
+        }
+    }
 
+    /// One queue per worker of the pool and one per lane, and the routes of the stored
+    /// objects shared by the callers and the workers.
     pub struct ExecutorChannel {
-        channel: (kanal::Sender<JvmCallEvent>, kanal::Receiver<JvmCallEvent>),
+        workers: Vec<WorkerChannel>,
+        lanes: Vec<WorkerChannel>,
+        routes: ObjectRoutes,
     }
 
     impl ExecutorChannel {
-        pub fn new() -> Self {
+        pub fn new(pool_size: usize, lanes: &[String]) -> Self {
             ExecutorChannel {
-                channel: kanal::unbounded(),
+                workers: (0..pool_size.max(1))
+                    .map(|index| WorkerChannel::new(index.to_string()))
+                    .collect(),
+                lanes: lanes
+                    .iter()
+                    .map(|lane| WorkerChannel::new(lane.clone()))
+                    .collect(),
+                routes: ObjectRoutes::default(),
             }
         }
 
-        pub fn get_receiver(&self) -> ExecutorReceiver {
-            ExecutorReceiver::new(self.channel.1.clone())
+        /// The receivers of the pool workers, then the ones of the lanes, with their names.
+        pub fn get_receivers(&self) -> Vec<(String, ExecutorReceiver)> {
+            self.workers
+                .iter()
+                .chain(self.lanes.iter())
+                .map(|worker| worker.receiver(&self.routes))
+                .collect()
         }
 
-        pub fn get_jvm_caller(&self) -> JvmCaller {
-            JvmCaller::new(self.channel.0.clone())
+        pub fn get_jvm_caller(&self, timeout: Option<Duration>) -> JvmCaller {
+            let workers = self.workers.iter().map(WorkerChannel::queue).collect();
+            let lanes = self
+                .lanes
+                .iter()
+                .map(|lane| (lane.name.clone(), lane.queue()))
+                .collect();
+            JvmCaller::new(
+                Dispatcher::new(workers, lanes, self.routes.clone()),
+                timeout,
+            )
         }
     }
 }
 
 mod jvm_caller {
+    use std::future::Future;
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
     use super::*;
-    use crate::{JavaArgs, ReturnType};
+    use crate::jni_methods_cache::thread_pool::{Dispatcher, Route};
+    use crate::{JavaArgs, JavaFieldRef, JavaMethodRef, MethodHandle, ObjectId};
 
+    /// Sends the calls to the executor. Every call waits on a response channel of its own,
+    /// so concurrent callers never receive each other's answers.
+    ///
+    /// A call not started by its deadline is dropped from the queue, a call started but not
+    /// answered in time leaves its caller with Timeout.
     pub struct JvmCaller {
-        event_channel: kanal::Sender<JvmCallEvent>,
-        jvm_result: JVMResult,
+        dispatcher: Arc<Dispatcher>,
+        route: Route,
+        /// Deadline of every call, counted from the moment it is sent.
+        timeout: Option<Duration>,
+        /// Deadline shared by every call.
+        deadline: Option<Instant>,
     }
     impl JvmCaller {
-        pub fn new(sender: kanal::Sender<JvmCallEvent>) -> Self {
+        pub fn new(dispatcher: Dispatcher, timeout: Option<Duration>) -> Self {
+            JvmCaller {
+                dispatcher: Arc::new(dispatcher),
+                route: Route::Pool,
+                timeout,
+                deadline: None,
+            }
+        }
+
+        /// Caller on the same executor whose calls time out after timeout.
+        pub fn with_timeout(&self, timeout: Duration) -> Self {
+            JvmCaller {
+                dispatcher: self.dispatcher.clone(),
+                route: self.route,
+                timeout: Some(timeout),
+                deadline: self.deadline,
+            }
+        }
+
+        /// Caller on the same executor whose calls time out at deadline.
+        pub fn with_deadline(&self, deadline: Instant) -> Self {
             JvmCaller {
-                event_channel: sender,
-                jvm_result: JVMResult::new(),
+                dispatcher: self.dispatcher.clone(),
+                route: self.route,
+                timeout: self.timeout,
+                deadline: Some(deadline),
+            }
+        }
+
+        /// Caller on the same executor whose calls run on the thread of lane, None when
+        /// the lane is not declared in the config.
+        pub fn on_lane(&self, lane: &str) -> Option<Self> {
+            Some(JvmCaller {
+                dispatcher: self.dispatcher.clone(),
+                route: self.dispatcher.lane(lane)?,
+                timeout: self.timeout,
+                deadline: self.deadline,
+            })
+        }
+
+        /// The earliest of the caller deadline and of the timeout counted from now.
+        fn call_deadline(&self) -> Option<Instant> {
+            let timeout = self.timeout.map(|timeout| Instant::now() + timeout);
+            match (timeout, self.deadline) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
             }
         }
         pub fn call_static_method<T: 'static + JVMResponse>(
@@ -71,33 +335,358 @@ mod jvm_caller {
             method_name: &str,
             sig: &str,
             args: JavaArgs,
-            return_type: ReturnType,
-            returned_object_id: Option<String>,
-        ) -> Result<T, ()> {
-            let instant = std::time::Instant::now();
-            let msg = JvmCallEvent::CallStaticMethod {
-                response_channel: self.jvm_result.get_sender(),
-                class_name: class_name.to_owned(),
-                method_name: method_name.to_owned(),
-                sig: sig.to_owned(),
+            returned_object_id: Option<ObjectId>,
+        ) -> Result<T, JniCacheError> {
+            self.call_static_method_ref::<T>(
+                JavaMethodRef::by_name(class_name, method_name, sig),
+                args,
+                returned_object_id,
+            )
+        }
+        pub fn call_static_method_by_handle<T: 'static + JVMResponse>(
+            &self,
+            handle: MethodHandle,
+            args: JavaArgs,
+            returned_object_id: Option<ObjectId>,
+        ) -> Result<T, JniCacheError> {
+            self.call_static_method_ref::<T>(handle.into(), args, returned_object_id)
+        }
+        pub fn call_method<T: 'static + JVMResponse>(
+            &self,
+            receiver_id: &ObjectId,
+            class_name: &str,
+            method_name: &str,
+            sig: &str,
+            args: JavaArgs,
+            returned_object_id: Option<ObjectId>,
+        ) -> Result<T, JniCacheError> {
+            self.call_method_ref::<T>(
+                receiver_id,
+                JavaMethodRef::by_name(class_name, method_name, sig),
                 args,
-                return_type,
                 returned_object_id,
+            )
+        }
+        pub fn call_method_by_handle<T: 'static + JVMResponse>(
+            &self,
+            receiver_id: &ObjectId,
+            handle: MethodHandle,
+            args: JavaArgs,
+            returned_object_id: Option<ObjectId>,
+        ) -> Result<T, JniCacheError> {
+            self.call_method_ref::<T>(receiver_id, handle.into(), args, returned_object_id)
+        }
+
+        pub fn new_object(
+            &self,
+            class_name: &str,
+            sig: &str,
+            args: JavaArgs,
+            returned_object_id: Option<ObjectId>,
+        ) -> Result<ObjectId, JniCacheError> {
+            self.new_object_ref(
+                JavaMethodRef::by_name(class_name, "<init>", sig),
+                args,
+                returned_object_id,
+            )
+        }
+        pub fn new_object_by_handle(
+            &self,
+            handle: MethodHandle,
+            args: JavaArgs,
+            returned_object_id: Option<ObjectId>,
+        ) -> Result<ObjectId, JniCacheError> {
+            self.new_object_ref(handle.into(), args, returned_object_id)
+        }
+
+        pub fn get_static_field<T: 'static + JVMResponse>(
+            &self,
+            field: JavaFieldRef,
+            returned_object_id: Option<ObjectId>,
+        ) -> Result<T, JniCacheError> {
+            self.get_field_ref::<T>(None, field, returned_object_id)
+        }
+        pub fn get_field<T: 'static + JVMResponse>(
+            &self,
+            receiver_id: &ObjectId,
+            field: JavaFieldRef,
+            returned_object_id: Option<ObjectId>,
+        ) -> Result<T, JniCacheError> {
+            self.get_field_ref::<T>(Some(receiver_id.clone()), field, returned_object_id)
+        }
+        pub fn set_static_field(
+            &self,
+            field: JavaFieldRef,
+            value: JavaArgs,
+        ) -> Result<(), JniCacheError> {
+            self.set_field_ref(None, field, value)
+        }
+        pub fn set_field(
+            &self,
+            receiver_id: &ObjectId,
+            field: JavaFieldRef,
+            value: JavaArgs,
+        ) -> Result<(), JniCacheError> {
+            self.set_field_ref(Some(receiver_id.clone()), field, value)
+        }
+
+        /// Resolves the registration on the executor thread and waits for the result, until
+        /// the deadline of the caller. A registration that runs late is still cached.
+        pub fn register(&self, registration: Registration) -> Result<Registered, JniCacheError> {
+            let (response_channel, response) = kanal::bounded(1);
+            let deadline = self.call_deadline();
+            self.dispatcher
+                .send(
+                    self.route,
+                    JvmCallEvent::Register {
+                        response_channel,
+                        registration,
+                        deadline,
+                    },
+                )
+                .map_err(|_| JniCacheError::ExecutorDisconnected)?;
+
+            match deadline {
+                Some(deadline) => {
+                    let timeout = deadline.saturating_duration_since(Instant::now());
+                    response.recv_timeout(timeout).map_err(|e| match e {
+                        kanal::ReceiveErrorTimeout::Timeout => JniCacheError::Timeout,
+                        _ => JniCacheError::ExecutorDisconnected,
+                    })?
+                }
+                None => response
+                    .recv()
+                    .map_err(|_| JniCacheError::ExecutorDisconnected)?,
+            }
+        }
+
+        fn get_field_ref<T: 'static + JVMResponse>(
+            &self,
+            receiver_id: Option<ObjectId>,
+            field: JavaFieldRef,
+            returned_object_id: Option<ObjectId>,
+        ) -> Result<T, JniCacheError> {
+            let (response_channel, jvm_result) = JVMResult::new();
+            let instant = std::time::Instant::now();
+            let msg = JvmCallEvent::GetField {
+                response_channel,
+                receiver_id,
+                field,
+                returned_object_id,
+                instant,
+                deadline: self.call_deadline(),
+            };
+
+            self.send_and_wait::<T>(msg, jvm_result)
+        }
+        fn set_field_ref(
+            &self,
+            receiver_id: Option<ObjectId>,
+            field: JavaFieldRef,
+            value: JavaArgs,
+        ) -> Result<(), JniCacheError> {
+            let (response_channel, jvm_result) = JVMResult::new();
+            let instant = std::time::Instant::now();
+            let msg = JvmCallEvent::SetField {
+                response_channel,
+                receiver_id,
+                field,
+                value,
                 instant,
+                deadline: self.call_deadline(),
             };
 
-            if let Err(e) = self.event_channel.send(msg) {
-                println!("error in calling jvm")
+            self.send_and_wait::<()>(msg, jvm_result)
+        }
+
+        /// Same as call_static_method, the returned future is resolved by the executor
+        /// without blocking the thread polling it.
+        pub fn call_static_method_async<T: 'static + JVMResponse>(
+            &self,
+            class_name: &str,
+            method_name: &str,
+            sig: &str,
+            args: JavaArgs,
+            returned_object_id: Option<ObjectId>,
+        ) -> impl Future<Output = Result<T, JniCacheError>> + Send + 'static {
+            let method = JavaMethodRef::by_name(class_name, method_name, sig);
+            let (response_channel, jvm_result) = JVMAsyncResult::new();
+            let event =
+                self.call_static_method_event(response_channel, method, args, returned_object_id);
+            self.send_async::<T>(event, jvm_result)
+        }
+        pub fn call_static_method_by_handle_async<T: 'static + JVMResponse>(
+            &self,
+            handle: MethodHandle,
+            args: JavaArgs,
+            returned_object_id: Option<ObjectId>,
+        ) -> impl Future<Output = Result<T, JniCacheError>> + Send + 'static {
+            let (response_channel, jvm_result) = JVMAsyncResult::new();
+            let event = self.call_static_method_event(
+                response_channel,
+                handle.into(),
+                args,
+                returned_object_id,
+            );
+            self.send_async::<T>(event, jvm_result)
+        }
+        /// Same as call_method, the returned future is resolved by the executor
+        /// without blocking the thread polling it.
+        pub fn call_method_async<T: 'static + JVMResponse>(
+            &self,
+            receiver_id: &ObjectId,
+            class_name: &str,
+            method_name: &str,
+            sig: &str,
+            args: JavaArgs,
+            returned_object_id: Option<ObjectId>,
+        ) -> impl Future<Output = Result<T, JniCacheError>> + Send + 'static {
+            let method = JavaMethodRef::by_name(class_name, method_name, sig);
+            let (response_channel, jvm_result) = JVMAsyncResult::new();
+            let event = self.call_method_event(
+                response_channel,
+                receiver_id,
+                method,
+                args,
+                returned_object_id,
+            );
+            self.send_async::<T>(event, jvm_result)
+        }
+        pub fn call_method_by_handle_async<T: 'static + JVMResponse>(
+            &self,
+            receiver_id: &ObjectId,
+            handle: MethodHandle,
+            args: JavaArgs,
+            returned_object_id: Option<ObjectId>,
+        ) -> impl Future<Output = Result<T, JniCacheError>> + Send + 'static {
+            let (response_channel, jvm_result) = JVMAsyncResult::new();
+            let event = self.call_method_event(
+                response_channel,
+                receiver_id,
+                handle.into(),
+                args,
+                returned_object_id,
+            );
+            self.send_async::<T>(event, jvm_result)
+        }
+
+        fn call_static_method_event(
+            &self,
+            response_channel: JVMResultSender,
+            method: JavaMethodRef,
+            args: JavaArgs,
+            returned_object_id: Option<ObjectId>,
+        ) -> JvmCallEvent {
+            JvmCallEvent::CallStaticMethod {
+                response_channel,
+                method,
+                args,
+                returned_object_id,
+                instant: std::time::Instant::now(),
+                deadline: self.call_deadline(),
+            }
+        }
+        fn call_method_event(
+            &self,
+            response_channel: JVMResultSender,
+            receiver_id: &ObjectId,
+            method: JavaMethodRef,
+            args: JavaArgs,
+            returned_object_id: Option<ObjectId>,
+        ) -> JvmCallEvent {
+            JvmCallEvent::CallMethod {
+                response_channel,
+                receiver_id: receiver_id.clone(),
+                method,
+                args,
+                returned_object_id,
+                instant: std::time::Instant::now(),
+                deadline: self.call_deadline(),
             }
+        }
 
-            if let Ok(res) = self.jvm_result.wait_for_result() {
-                if let Ok(r) = res.to_value() {
-                    Ok(*r)
-                } else {
-                    Err(())
-                }
-            } else {
-                Err(())
+        fn call_static_method_ref<T: 'static + JVMResponse>(
+            &self,
+            method: JavaMethodRef,
+            args: JavaArgs,
+            returned_object_id: Option<ObjectId>,
+        ) -> Result<T, JniCacheError> {
+            let (response_channel, jvm_result) = JVMResult::new();
+            let msg =
+                self.call_static_method_event(response_channel, method, args, returned_object_id);
+
+            self.send_and_wait::<T>(msg, jvm_result)
+        }
+        fn call_method_ref<T: 'static + JVMResponse>(
+            &self,
+            receiver_id: &ObjectId,
+            method: JavaMethodRef,
+            args: JavaArgs,
+            returned_object_id: Option<ObjectId>,
+        ) -> Result<T, JniCacheError> {
+            let (response_channel, jvm_result) = JVMResult::new();
+            let msg = self.call_method_event(
+                response_channel,
+                receiver_id,
+                method,
+                args,
+                returned_object_id,
+            );
+
+            self.send_and_wait::<T>(msg, jvm_result)
+        }
+
+        fn new_object_ref(
+            &self,
+            constructor: JavaMethodRef,
+            args: JavaArgs,
+            returned_object_id: Option<ObjectId>,
+        ) -> Result<ObjectId, JniCacheError> {
+            let (response_channel, jvm_result) = JVMResult::new();
+            let instant = std::time::Instant::now();
+            let msg = JvmCallEvent::NewObject {
+                response_channel,
+                constructor,
+                args,
+                returned_object_id,
+                instant,
+                deadline: self.call_deadline(),
+            };
+
+            self.send_and_wait::<ObjectId>(msg, jvm_result)
+        }
+
+        /// The executor answers every event once ; when it is gone, the response channel
+        /// is dropped with the event and the caller gets ExecutorDisconnected.
+        fn send_and_wait<T: 'static + JVMResponse>(
+            &self,
+            msg: JvmCallEvent,
+            jvm_result: JVMResult,
+        ) -> Result<T, JniCacheError> {
+            let deadline = msg.deadline();
+            self.dispatcher
+                .send(self.route, msg)
+                .map_err(|_| JniCacheError::ExecutorDisconnected)?;
+
+            let res = jvm_result.wait_for_result(deadline)?;
+            T::from_returned_value(res)
+        }
+
+        /// Queues the event right away, it answers to its own channel ; the returned
+        /// future only waits for the answer. It resolves with Timeout at the deadline of the
+        /// call, whether the call is still queued or already running.
+        fn send_async<T: 'static + JVMResponse>(
+            &self,
+            event: JvmCallEvent,
+            jvm_result: JVMAsyncResult,
+        ) -> impl Future<Output = Result<T, JniCacheError>> + Send + 'static {
+            let deadline = event.deadline();
+            let sent = self.dispatcher.send(self.route, event);
+
+            async move {
+                sent.map_err(|_| JniCacheError::ExecutorDisconnected)?;
+                let res = jvm_result.wait_for_result(deadline).await?;
+                T::from_returned_value(res)
             }
         }
     }
@@ -105,99 +694,313 @@ mod jvm_caller {
 
 mod executor_receiver {
     use super::*;
+    use crate::jni_methods_cache::thread_pool::{ObjectRoutes, WorkerLoad, WorkerLoadGuard};
 
+    /// Queue of a worker of the pool.
     pub struct ExecutorReceiver {
         event_receiver: kanal::Receiver<JvmCallEvent>,
+        load: WorkerLoad,
+        routes: ObjectRoutes,
     }
     impl ExecutorReceiver {
-        pub fn new(receiver: kanal::Receiver<JvmCallEvent>) -> Self {
+        pub fn new(
+            receiver: kanal::Receiver<JvmCallEvent>,
+            load: WorkerLoad,
+            routes: ObjectRoutes,
+        ) -> Self {
             ExecutorReceiver {
                 event_receiver: receiver,
+                load,
+                routes,
             }
         }
 
-        pub fn receive(&self) -> Result<JvmCallEvent, kanal::ReceiveError> {
-            self.event_receiver.recv()
+        /// The event stays counted in the worker load, and in the calls on its stored
+        /// object, until the guard is dropped.
+        pub fn receive(&self) -> Result<(JvmCallEvent, WorkerLoadGuard), kanal::ReceiveError> {
+            let event = self.event_receiver.recv()?;
+            let bound = event
+                .receiver_id()
+                .map(|receiver_id| (self.routes.clone(), receiver_id.clone()));
+            Ok((event, self.load.running(bound)))
+        }
+    }
+
+    /// Dropped when the executor thread exits, or unwinds from a panic : closing the
+    /// channel drops the queued events with their response channels, so every waiting
+    /// caller gets ExecutorDisconnected, and the next calls fail to be sent.
+    impl Drop for ExecutorReceiver {
+        fn drop(&mut self) {
+            if self.event_receiver.close().is_ok() {
+                log::info!("jvm executor stopped, the pending calls are disconnected");
+            }
         }
     }
 }
 
 mod event_handler {
-    use jvm_method_caller::call_java_static_method_internal;
+    use jni::JNIEnv;
+    use jvm_method_caller::{
+        call_java_method_internal, call_java_static_method_internal, get_java_field_internal,
+        new_java_object_internal, register_internal, set_java_field_internal,
+    };
 
     use super::*;
+    use crate::jni_methods_cache::config::JniCacheConfig;
+    use crate::{JavaMethodCache, ObjectId, ReturnedValue};
 
-    pub fn run(event_receiver: ExecutorReceiver) {
-        while let Ok(event) = event_receiver.receive() {
+    pub fn run(
+        event_receiver: ExecutorReceiver,
+        env: &mut JNIEnv,
+        cache: &JavaMethodCache,
+        config: &JniCacheConfig,
+    ) {
+        // the worker load goes down once the event is answered and _running dropped
+        while let Ok((event, _running)) = event_receiver.receive() {
+            if event.is_expired() {
+                event.expire();
+                continue;
+            }
+            // every local ref created by the call is freed when its frame is popped
+            let capacity = config.local_frame_capacity;
             match event {
                 JvmCallEvent::CallStaticMethod {
                     response_channel,
-                    class_name,
-                    method_name,
-                    sig,
+                    method,
                     args,
-                    return_type,
                     returned_object_id,
-                    instant,
+                    ..
+                } => {
+                    let res = env.with_local_frame(capacity, |env| {
+                        call_java_static_method_internal(
+                            env,
+                            cache,
+                            &method,
+                            args,
+                            returned_object_id.clone(),
+                        )
+                    });
+                    answer(
+                        cache,
+                        response_channel,
+                        res,
+                        returned_object_id.as_ref(),
+                        &method.method_name(),
+                    );
+                }
+                JvmCallEvent::CallMethod {
+                    response_channel,
+                    receiver_id,
+                    method,
+                    args,
+                    returned_object_id,
+                    ..
+                } => {
+                    let res = env.with_local_frame(capacity, |env| {
+                        call_java_method_internal(
+                            env,
+                            cache,
+                            &receiver_id,
+                            &method,
+                            args,
+                            returned_object_id.clone(),
+                        )
+                    });
+                    answer(
+                        cache,
+                        response_channel,
+                        res,
+                        returned_object_id.as_ref(),
+                        &method.method_name(),
+                    );
+                }
+                JvmCallEvent::NewObject {
+                    response_channel,
+                    constructor,
+                    args,
+                    returned_object_id,
+                    ..
                 } => {
-                    if let Ok(res) = call_java_static_method_internal(
-                        class_name.as_str(),
-                        method_name.as_str(),
-                        sig.as_str(),
-                        args,
-                        return_type,
-                        returned_object_id,
-                    ) {
-                        if let Ok(_) = response_channel.send(res) {}
+                    let res = env.with_local_frame(capacity, |env| {
+                        new_java_object_internal(
+                            env,
+                            cache,
+                            &constructor,
+                            args,
+                            returned_object_id.clone(),
+                        )
+                    });
+                    answer(
+                        cache,
+                        response_channel,
+                        res,
+                        returned_object_id.as_ref(),
+                        &constructor.method_name(),
+                    );
+                }
+                JvmCallEvent::GetField {
+                    response_channel,
+                    receiver_id,
+                    field,
+                    returned_object_id,
+                    ..
+                } => {
+                    let res = env.with_local_frame(capacity, |env| {
+                        get_java_field_internal(
+                            env,
+                            cache,
+                            receiver_id.as_ref(),
+                            &field,
+                            returned_object_id.clone(),
+                        )
+                    });
+                    answer(
+                        cache,
+                        response_channel,
+                        res,
+                        returned_object_id.as_ref(),
+                        &field.field_name(),
+                    );
+                }
+                JvmCallEvent::SetField {
+                    response_channel,
+                    receiver_id,
+                    field,
+                    value,
+                    ..
+                } => {
+                    let res = env.with_local_frame(capacity, |env| {
+                        set_java_field_internal(env, cache, receiver_id.as_ref(), &field, value)
+                    });
+                    if response_channel.send(res).is_err() {
+                        log::debug!("caller of [{}] is no longer waiting", field.field_name());
+                    }
+                }
+                JvmCallEvent::Register {
+                    response_channel,
+                    registration,
+                    ..
+                } => {
+                    let res = env.with_local_frame(capacity, |env| {
+                        register_internal(env, cache, &registration)
+                    });
+                    if response_channel.send(res).is_err() {
+                        log::debug!("caller of [{:?}] is no longer waiting", registration);
                     }
                 }
-                _ => {}
+            }
+        }
+    }
+
+    /// Sends the result to the caller. When the caller stopped waiting, an object stored
+    /// under an id minted by the call is removed, nobody could ever use or release it.
+    fn answer(
+        cache: &JavaMethodCache,
+        response_channel: JVMResultSender,
+        res: Result<ReturnedValue, JniCacheError>,
+        returned_object_id: Option<&ObjectId>,
+        member: &str,
+    ) {
+        let minted_id = match (&res, returned_object_id) {
+            (Ok(ReturnedValue::JObject(id)), None) => Some(id.clone()),
+            _ => None,
+        };
+        if response_channel.send(res).is_err() {
+            log::debug!("caller of [{}] is no longer waiting", member);
+            if let Some(id) = minted_id {
+                cache.remove_object(&id);
             }
         }
     }
 }
 
 mod jvm_method_caller {
-    use crate::jni_methods_cache::JAVAMETHODCACHE;
-    use crate::jni_methods_cache::{JavaArgs, ReturnType, ReturnedValue};
+    use jni::JNIEnv;
+
+    use super::jvm_call_event::{Registered, Registration};
+    use crate::jni_methods_cache::error::JniCacheError;
+    use crate::{JavaArgs, JavaFieldRef, JavaMethodCache, JavaMethodRef, ObjectId, ReturnedValue};
 
     pub fn call_java_static_method_internal(
-        class_name: &str,
-        method_name: &str,
-        sig: &str,
+        env: &mut JNIEnv,
+        cache: &JavaMethodCache,
+        method: &JavaMethodRef,
         args: JavaArgs,
-        return_type: ReturnType,
-        returned_object_id: Option<String>,
-    ) -> std::result::Result<ReturnedValue, String> {
-        let res = unsafe {
-            JAVAMETHODCACHE.call_static_method(
-                class_name,
-                method_name,
-                sig,
-                args,
-                return_type,
-                returned_object_id,
-            )
-        };
-        res
+        returned_object_id: Option<ObjectId>,
+    ) -> std::result::Result<ReturnedValue, JniCacheError> {
+        cache.call_static_method(env, method, args, returned_object_id)
     }
-    pub fn call_java_method(
-        class_name: &str,
-        method_name: &str,
-        sig: &str,
+    pub fn call_java_method_internal(
+        env: &mut JNIEnv,
+        cache: &JavaMethodCache,
+        receiver_id: &ObjectId,
+        method: &JavaMethodRef,
         args: JavaArgs,
-        return_type: ReturnType,
-        returned_object_id: Option<String>,
-    ) -> std::result::Result<ReturnedValue, String> {
-        unsafe {
-            JAVAMETHODCACHE.call_method(
-                class_name,
-                method_name,
+        returned_object_id: Option<ObjectId>,
+    ) -> std::result::Result<ReturnedValue, JniCacheError> {
+        cache.call_method(env, receiver_id, method, args, returned_object_id)
+    }
+    pub fn new_java_object_internal(
+        env: &mut JNIEnv,
+        cache: &JavaMethodCache,
+        constructor: &JavaMethodRef,
+        args: JavaArgs,
+        returned_object_id: Option<ObjectId>,
+    ) -> std::result::Result<ReturnedValue, JniCacheError> {
+        cache
+            .new_object(env, constructor, args, returned_object_id)
+            .map(ReturnedValue::JObject)
+    }
+    pub fn get_java_field_internal(
+        env: &mut JNIEnv,
+        cache: &JavaMethodCache,
+        receiver_id: Option<&ObjectId>,
+        field: &JavaFieldRef,
+        returned_object_id: Option<ObjectId>,
+    ) -> std::result::Result<ReturnedValue, JniCacheError> {
+        match receiver_id {
+            Some(receiver_id) => cache.get_field(env, receiver_id, field, returned_object_id),
+            None => cache.get_static_field(env, field, returned_object_id),
+        }
+    }
+    pub fn set_java_field_internal(
+        env: &mut JNIEnv,
+        cache: &JavaMethodCache,
+        receiver_id: Option<&ObjectId>,
+        field: &JavaFieldRef,
+        value: JavaArgs,
+    ) -> std::result::Result<ReturnedValue, JniCacheError> {
+        match receiver_id {
+            Some(receiver_id) => cache.set_field(env, receiver_id, field, value),
+            None => cache.set_static_field(env, field, value),
+        }
+        .map(|_| ReturnedValue::Void)
+    }
+    pub fn register_internal(
+        env: &mut JNIEnv,
+        cache: &JavaMethodCache,
+        registration: &Registration,
+    ) -> std::result::Result<Registered, JniCacheError> {
+        match registration {
+            Registration::StandardClass(class_name) => cache
+                .register_standard_class(env, class_name)
+                .map(|_| Registered::StandardClass),
+            Registration::Method {
+                method_type,
+                class,
+                name,
                 sig,
-                args,
-                return_type,
-                returned_object_id,
-            )
+            } => cache
+                .register_method(env, *method_type, class, name, sig)
+                .map(Registered::Method),
+            Registration::Field {
+                field_type,
+                class,
+                name,
+                sig,
+            } => cache
+                .register_field(env, *field_type, class, name, sig)
+                .map(Registered::Field),
         }
     }
 }
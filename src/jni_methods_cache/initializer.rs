@@ -0,0 +1,102 @@
+/***** Platform independent initialization ******/
+use std::sync::{Arc, OnceLock};
+
+use jni::objects::GlobalRef;
+use parking_lot::Mutex;
+
+use crate::jni_methods_cache::config::JniCacheConfig;
+use crate::jni_methods_cache::error::JniCacheError;
+use crate::jni_methods_cache::executor::ExecutorChannel;
+use crate::jni_methods_cache::jni_cache::{JniCache, JNI_CACHE};
+use crate::jni_methods_cache::methods_cache::JavaMethodCacheBuilder;
+use crate::jni_methods_cache::thread_pool;
+
+/// Held while a cache is built, so that a single init builds it at a time.
+static JNI_CACHE_INIT: Mutex<()> = Mutex::new(());
+
+impl JniCache {
+    /// Builds the cache on a new executor thread attached to the given JavaVM, the other
+    /// threads of the executor pool are started once it is built.
+    /// The custom classes are loaded through class_loader, or through the system class
+    /// loader when none is given (desktop JVM started with the invocation feature).
+    /// Waits for the build : a panic of build_cb or of the resolution of the registered
+    /// members is returned as BuildFailed.
+    pub fn init_with_vm(
+        java_vm: jni::JavaVM,
+        class_loader: Option<GlobalRef>,
+        build_cb: impl FnOnce(&mut JavaMethodCacheBuilder) + std::marker::Send + 'static,
+    ) -> std::result::Result<JniCache, JniCacheError> {
+        Self::init_with_config(java_vm, class_loader, JniCacheConfig::default(), build_cb)
+    }
+
+    /// Same as init_with_vm, with the given executor settings.
+    pub fn init_with_config(
+        java_vm: jni::JavaVM,
+        class_loader: Option<GlobalRef>,
+        config: JniCacheConfig,
+        build_cb: impl FnOnce(&mut JavaMethodCacheBuilder) + std::marker::Send + 'static,
+    ) -> std::result::Result<JniCache, JniCacheError> {
+        Self::init_once(java_vm, class_loader, None, config, build_cb)
+    }
+
+    /// Same as init_with_vm, from a raw JavaVM pointer.
+    ///
+    /// # Safety
+    /// java_vm_ptr has to be a valid pointer to a JavaVM that outlives the cache.
+    pub unsafe fn init_with_raw_vm(
+        java_vm_ptr: *mut jni::sys::JavaVM,
+        class_loader: Option<GlobalRef>,
+        build_cb: impl FnOnce(&mut JavaMethodCacheBuilder) + std::marker::Send + 'static,
+    ) -> std::result::Result<JniCache, JniCacheError> {
+        let java_vm = jni::JavaVM::from_raw(java_vm_ptr)?;
+        Self::init_with_vm(java_vm, class_loader, build_cb)
+    }
+
+    /// Spawns the executor and builds the cache, only once : the next calls return the
+    /// cache of the first one. activity is stored as "native_activity".
+    /// A cache whose build failed is not kept, the next call builds a new one.
+    pub(crate) fn init_once(
+        java_vm: jni::JavaVM,
+        class_loader: Option<GlobalRef>,
+        activity: Option<GlobalRef>,
+        config: JniCacheConfig,
+        build_cb: impl FnOnce(&mut JavaMethodCacheBuilder) + std::marker::Send + 'static,
+    ) -> std::result::Result<JniCache, JniCacheError> {
+        let _init = JNI_CACHE_INIT.lock();
+        if let Some(jni_cache) = JNI_CACHE.get() {
+            return Ok(jni_cache.clone());
+        }
+        let jni_cache = Self::spawn_executor(java_vm, class_loader, activity, config, build_cb)?;
+        Ok(JNI_CACHE.get_or_init(|| jni_cache).clone())
+    }
+
+    fn spawn_executor(
+        java_vm: jni::JavaVM,
+        class_loader: Option<GlobalRef>,
+        activity: Option<GlobalRef>,
+        config: JniCacheConfig,
+        build_cb: impl FnOnce(&mut JavaMethodCacheBuilder) + std::marker::Send + 'static,
+    ) -> std::result::Result<JniCache, JniCacheError> {
+        let java_vm = Arc::new(java_vm);
+        let cache = Arc::new(OnceLock::new());
+        let executor_channel = ExecutorChannel::new(config.pool_size, &config.lanes);
+        let executor_receivers = executor_channel.get_receivers();
+        let jvm_caller = executor_channel.get_jvm_caller(config.call_timeout);
+
+        let resolve_on_first_call = config.resolve_on_first_call;
+        thread_pool::spawn_workers(
+            java_vm.clone(),
+            cache.clone(),
+            config,
+            executor_receivers,
+            move |env| {
+                let mut java_method_builder = JavaMethodCacheBuilder::new(class_loader, activity);
+                java_method_builder.resolve_on_first_call = resolve_on_first_call;
+                build_cb(&mut java_method_builder);
+                java_method_builder.build(env)
+            },
+        )?;
+
+        Ok(JniCache::new(java_vm, jvm_caller, cache))
+    }
+}
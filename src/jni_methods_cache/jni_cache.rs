@@ -0,0 +1,370 @@
+/***** Shared handle on the cache and its executor ******/
+use std::future::Future;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+use jni::objects::GlobalRef;
+
+use crate::jni_methods_cache::error::JniCacheError;
+use crate::jni_methods_cache::executor::{JvmCaller, Registered, Registration};
+use crate::jni_methods_cache::methods_cache::java_method_cache_utils::{
+    JavaArgs, JavaFieldRef, MethodHandle, ObjectId,
+};
+use crate::jni_methods_cache::methods_cache::{JVMResponse, JavaMethodCache};
+
+/// The cache initialized by JniCache::init*, only one per process.
+pub(crate) static JNI_CACHE: OnceLock<JniCache> = OnceLock::new();
+
+/// Cloneable handle on the java vm, the cache and the executor thread the calls are sent to.
+/// It can be shared between threads, every clone designates the same cache.
+#[derive(Clone)]
+pub struct JniCache {
+    java_vm: Arc<jni::JavaVM>,
+    jvm_caller: Arc<JvmCaller>,
+    cache: Arc<OnceLock<JavaMethodCache>>,
+}
+
+impl JniCache {
+    pub(crate) fn new(
+        java_vm: Arc<jni::JavaVM>,
+        jvm_caller: JvmCaller,
+        cache: Arc<OnceLock<JavaMethodCache>>,
+    ) -> Self {
+        Self {
+            java_vm,
+            jvm_caller: Arc::new(jvm_caller),
+            cache,
+        }
+    }
+
+    /// The cache initialized in this process, if any.
+    pub fn global() -> Option<JniCache> {
+        JNI_CACHE.get().cloned()
+    }
+
+    /// Handle on the same cache whose calls fail with Timeout when not answered within
+    /// timeout. A call still queued when it expires is dropped without running.
+    pub fn with_timeout(&self, timeout: Duration) -> JniCache {
+        JniCache {
+            java_vm: self.java_vm.clone(),
+            jvm_caller: Arc::new(self.jvm_caller.with_timeout(timeout)),
+            cache: self.cache.clone(),
+        }
+    }
+
+    /// Handle on the same cache whose calls fail with Timeout when not answered by deadline.
+    pub fn with_deadline(&self, deadline: Instant) -> JniCache {
+        JniCache {
+            java_vm: self.java_vm.clone(),
+            jvm_caller: Arc::new(self.jvm_caller.with_deadline(deadline)),
+            cache: self.cache.clone(),
+        }
+    }
+
+    /// Handle on the same cache whose calls run on the dedicated thread of lane, in the
+    /// order they are sent.
+    pub fn on_lane(&self, lane: &str) -> std::result::Result<JniCache, JniCacheError> {
+        let jvm_caller = self
+            .jvm_caller
+            .on_lane(lane)
+            .ok_or_else(|| JniCacheError::UnknownLane(lane.to_string()))?;
+        Ok(JniCache {
+            java_vm: self.java_vm.clone(),
+            jvm_caller: Arc::new(jvm_caller),
+            cache: self.cache.clone(),
+        })
+    }
+
+    pub fn java_vm(&self) -> &jni::JavaVM {
+        &self.java_vm
+    }
+
+    /// True once the executor thread has built the cache. Calls sent before are
+    /// queued and run after the build.
+    pub fn is_built(&self) -> bool {
+        self.cache.get().is_some()
+    }
+
+    pub fn print_method_list(&self) {
+        match self.cache.get() {
+            Some(cache) => cache.print_method_list(),
+            None => println!("JavaMethodCache is not built yet"),
+        }
+    }
+
+    pub(crate) fn register(
+        &self,
+        registration: Registration,
+    ) -> std::result::Result<Registered, JniCacheError> {
+        self.jvm_caller.register(registration)
+    }
+
+    fn built_cache(&self) -> std::result::Result<&JavaMethodCache, JniCacheError> {
+        self.cache.get().ok_or(JniCacheError::CacheNotBuilt)
+    }
+
+    pub fn find_object(
+        &self,
+        id: impl Into<ObjectId>,
+    ) -> std::result::Result<Option<GlobalRef>, JniCacheError> {
+        Ok(self.built_cache()?.find_object(&id.into()))
+    }
+
+    /// Stores object under id in one step and gives back the object it replaces.
+    pub fn replace_object(
+        &self,
+        id: impl Into<ObjectId>,
+        object: GlobalRef,
+    ) -> std::result::Result<Option<GlobalRef>, JniCacheError> {
+        Ok(self.built_cache()?.replace_object(&id.into(), object))
+    }
+
+    /// Removes the object from the store, the java reference is deleted once the
+    /// returned GlobalRef is dropped.
+    pub fn remove_object(
+        &self,
+        id: impl Into<ObjectId>,
+    ) -> std::result::Result<Option<GlobalRef>, JniCacheError> {
+        Ok(self.built_cache()?.remove_object(&id.into()))
+    }
+
+    pub fn object_ids(&self) -> std::result::Result<Vec<ObjectId>, JniCacheError> {
+        Ok(self.built_cache()?.object_ids())
+    }
+
+    pub fn object_count(&self) -> std::result::Result<usize, JniCacheError> {
+        Ok(self.built_cache()?.object_count())
+    }
+
+    pub fn clear_objects(&self) -> std::result::Result<(), JniCacheError> {
+        self.built_cache()?.clear_objects();
+        Ok(())
+    }
+
+    /// Ties the stored object to a guard that removes it from the store when dropped.
+    pub fn guard_object(&self, id: impl Into<ObjectId>) -> StoredObject {
+        StoredObject {
+            cache: self.clone(),
+            id: Some(id.into()),
+        }
+    }
+
+    /// Creates an instance with the cached constructor of class_name and stores it under
+    /// returned_object_id, or under a minted id when None.
+    pub fn new_object(
+        &self,
+        class_name: &str,
+        sig: &str,
+        args: JavaArgs,
+        returned_object_id: Option<ObjectId>,
+    ) -> std::result::Result<ObjectId, JniCacheError> {
+        self.jvm_caller
+            .new_object(class_name, sig, args, returned_object_id)
+    }
+
+    pub fn new_object_by_handle(
+        &self,
+        handle: MethodHandle,
+        args: JavaArgs,
+        returned_object_id: Option<ObjectId>,
+    ) -> std::result::Result<ObjectId, JniCacheError> {
+        self.jvm_caller
+            .new_object_by_handle(handle, args, returned_object_id)
+    }
+
+    pub fn call_static_method<T: 'static + JVMResponse>(
+        &self,
+        class_name: &str,
+        method_name: &str,
+        sig: &str,
+        args: JavaArgs,
+        returned_object_id: Option<ObjectId>,
+    ) -> std::result::Result<T, JniCacheError> {
+        self.jvm_caller.call_static_method::<T>(
+            class_name,
+            method_name,
+            sig,
+            args,
+            returned_object_id,
+        )
+    }
+
+    pub fn call_method<T: 'static + JVMResponse>(
+        &self,
+        receiver_id: impl Into<ObjectId>,
+        class_name: &str,
+        method_name: &str,
+        sig: &str,
+        args: JavaArgs,
+        returned_object_id: Option<ObjectId>,
+    ) -> std::result::Result<T, JniCacheError> {
+        self.jvm_caller.call_method::<T>(
+            &receiver_id.into(),
+            class_name,
+            method_name,
+            sig,
+            args,
+            returned_object_id,
+        )
+    }
+
+    pub fn call_static_method_by_handle<T: 'static + JVMResponse>(
+        &self,
+        handle: MethodHandle,
+        args: JavaArgs,
+        returned_object_id: Option<ObjectId>,
+    ) -> std::result::Result<T, JniCacheError> {
+        self.jvm_caller
+            .call_static_method_by_handle::<T>(handle, args, returned_object_id)
+    }
+
+    pub fn call_method_by_handle<T: 'static + JVMResponse>(
+        &self,
+        receiver_id: impl Into<ObjectId>,
+        handle: MethodHandle,
+        args: JavaArgs,
+        returned_object_id: Option<ObjectId>,
+    ) -> std::result::Result<T, JniCacheError> {
+        self.jvm_caller.call_method_by_handle::<T>(
+            &receiver_id.into(),
+            handle,
+            args,
+            returned_object_id,
+        )
+    }
+
+    /// Same as call_static_method, without blocking : the call is queued right away and
+    /// the future, which can be polled by any async executor, resolves with its answer.
+    pub fn call_static_method_async<T: 'static + JVMResponse>(
+        &self,
+        class_name: &str,
+        method_name: &str,
+        sig: &str,
+        args: JavaArgs,
+        returned_object_id: Option<ObjectId>,
+    ) -> impl Future<Output = std::result::Result<T, JniCacheError>> + Send + 'static {
+        self.jvm_caller.call_static_method_async::<T>(
+            class_name,
+            method_name,
+            sig,
+            args,
+            returned_object_id,
+        )
+    }
+
+    pub fn call_method_async<T: 'static + JVMResponse>(
+        &self,
+        receiver_id: impl Into<ObjectId>,
+        class_name: &str,
+        method_name: &str,
+        sig: &str,
+        args: JavaArgs,
+        returned_object_id: Option<ObjectId>,
+    ) -> impl Future<Output = std::result::Result<T, JniCacheError>> + Send + 'static {
+        self.jvm_caller.call_method_async::<T>(
+            &receiver_id.into(),
+            class_name,
+            method_name,
+            sig,
+            args,
+            returned_object_id,
+        )
+    }
+
+    pub fn call_static_method_by_handle_async<T: 'static + JVMResponse>(
+        &self,
+        handle: MethodHandle,
+        args: JavaArgs,
+        returned_object_id: Option<ObjectId>,
+    ) -> impl Future<Output = std::result::Result<T, JniCacheError>> + Send + 'static {
+        self.jvm_caller
+            .call_static_method_by_handle_async::<T>(handle, args, returned_object_id)
+    }
+
+    pub fn call_method_by_handle_async<T: 'static + JVMResponse>(
+        &self,
+        receiver_id: impl Into<ObjectId>,
+        handle: MethodHandle,
+        args: JavaArgs,
+        returned_object_id: Option<ObjectId>,
+    ) -> impl Future<Output = std::result::Result<T, JniCacheError>> + Send + 'static {
+        self.jvm_caller.call_method_by_handle_async::<T>(
+            &receiver_id.into(),
+            handle,
+            args,
+            returned_object_id,
+        )
+    }
+
+    /// Reads a cached static field, an object value is stored under returned_object_id,
+    /// or under a minted id when None.
+    pub fn get_static_field<T: 'static + JVMResponse>(
+        &self,
+        field: impl Into<JavaFieldRef>,
+        returned_object_id: Option<ObjectId>,
+    ) -> std::result::Result<T, JniCacheError> {
+        self.jvm_caller
+            .get_static_field::<T>(field.into(), returned_object_id)
+    }
+
+    pub fn set_static_field(
+        &self,
+        field: impl Into<JavaFieldRef>,
+        value: JavaArgs,
+    ) -> std::result::Result<(), JniCacheError> {
+        self.jvm_caller.set_static_field(field.into(), value)
+    }
+
+    /// Reads a cached field of the stored object receiver_id.
+    pub fn get_field<T: 'static + JVMResponse>(
+        &self,
+        receiver_id: impl Into<ObjectId>,
+        field: impl Into<JavaFieldRef>,
+        returned_object_id: Option<ObjectId>,
+    ) -> std::result::Result<T, JniCacheError> {
+        self.jvm_caller
+            .get_field::<T>(&receiver_id.into(), field.into(), returned_object_id)
+    }
+
+    pub fn set_field(
+        &self,
+        receiver_id: impl Into<ObjectId>,
+        field: impl Into<JavaFieldRef>,
+        value: JavaArgs,
+    ) -> std::result::Result<(), JniCacheError> {
+        self.jvm_caller
+            .set_field(&receiver_id.into(), field.into(), value)
+    }
+}
+
+/// RAII guard of an object of the store : the object is removed, and its java reference
+/// deleted, when the guard is dropped.
+pub struct StoredObject {
+    cache: JniCache,
+    id: Option<ObjectId>,
+}
+
+impl StoredObject {
+    pub fn id(&self) -> &ObjectId {
+        self.id.as_ref().expect("StoredObject id already released")
+    }
+
+    /// Gives back the id without removing the object from the store.
+    pub fn into_id(mut self) -> ObjectId {
+        self.id.take().expect("StoredObject id already released")
+    }
+}
+
+impl Drop for StoredObject {
+    fn drop(&mut self) {
+        if let Some(id) = self.id.take() {
+            let _ = self.cache.remove_object(id);
+        }
+    }
+}
+
+impl std::fmt::Debug for StoredObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("StoredObject").field(&self.id).finish()
+    }
+}
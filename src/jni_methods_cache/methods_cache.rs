@@ -1,205 +1,137 @@
 use jni::objects::{
-    JClass, JDoubleArray, JMethodID, JObject, JStaticMethodID, JString, JValue, JValueOwned,
+    GlobalRef, JClass, JDoubleArray, JMethodID, JObject, JStaticMethodID, JString, JValue,
+    JValueOwned,
 };
-use jni::signature::ReturnType;
-use jni::sys::jobject;
-use jni::JavaVM;
+use jni::signature::{JavaType, Primitive, ReturnType, TypeSignature};
+use jni::JNIEnv;
+use parking_lot::{Mutex, RwLock};
+use std::collections::HashMap;
 use std::mem;
+use std::sync::Arc;
 
-use crate::jni_methods_cache::executor::ExecutorChannel;
+use crate::jni_methods_cache::error::JniCacheError;
+use crate::jni_methods_cache::typed_method::{
+    is_passable_as, is_typed_as, Field, JavaArgList, JavaReturn, Method, StaticField, StaticMethod,
+};
+pub use java_exception::check_exception;
 pub use java_method_build_tools::*;
+pub use java_vm_response::JVMAsyncResult;
 pub use java_vm_response::JVMResponse;
 pub use java_vm_response::JVMResult;
 pub use java_vm_response::JVMResultSender;
 
 pub mod java_method_build_tools {
-    use self::j_object_ref::{JavaMethodsList, JavaMethodsListRefs, JavaStaticMethodsList};
+    use std::str::FromStr;
+
+    use self::j_field_ref::{
+        field_return_type, parse_field_signature, FieldId, JavaField, JavaFieldsList,
+        JavaFieldsListRefs,
+    };
+    use self::j_object_ref::{
+        parse_method_signature, JavaMethod, JavaMethodsList, JavaMethodsListRefs, JavaStaticMethod,
+        JavaStaticMethodsList,
+    };
     use self::j_object_store::JObjectStore;
-    use self::java_method_cache_utils::{JavaArgs, MethodType, ReturnedValue};
+    use self::java_method_cache_utils::{
+        is_extractible, FieldHandle, FieldType, JavaArgs, JavaFieldRef, JavaMethodRef,
+        MethodHandle, MethodType, ObjectId, ReturnedValue,
+    };
     use self::native_class_finder::NativeClassFinder;
-    use self::standard_class_finder::{StandardClassCache, StandardClassPreList};
+    use self::standard_class_finder::{StandardClass, StandardClassCache, StandardClassPreList};
     use super::*;
 
-    #[derive(Default)]
-    pub enum JavaMethods<'a> {
-        Cache {
-            cache: JavaMethodCache<'a>,
-        },
-        #[default]
-        None,
-    }
-
-    impl<'a: 'static> JavaMethods<'a> {
-        pub fn build_cache(&'a mut self) {
-            match self {
-                JavaMethods::Cache { cache } => {
-                    if !cache.cache_builded {
-                        cache.standard_class_cache.build_standard_class_list(
-                            &mut cache.env_2,
-                            &cache.standard_class_pre_list,
-                        );
-                        cache.static_method_list.build_list_with_ref(
-                            &mut cache.env_2,
-                            &mut cache.java_methods_list_ref,
-                            &mut cache.native_class_finder,
-                        );
-                        cache.method_list.build_list_with_ref(
-                            &mut cache.env_2,
-                            &mut cache.java_methods_list_ref,
-                            &mut cache.native_class_finder_2,
-                        );
-                        cache.cache_builded = true;
-                        println!("JavaMethodCache is correctly initialized !");
-                    }
-                }
-                _ => {
-                    println!("Attemps to build cache but no cache instance is instanciated");
-                }
-            }
-        }
-        pub fn call_method(
-            &'a mut self,
-            class: &str,
-            name: &str,
-            sig: &str,
-            args: JavaArgs,
-            return_type: ReturnType,
-            returned_object_id: Option<String>, //in case of JObject as returned value -> id is a string
-                                                //to get the object stored
-        ) -> std::result::Result<ReturnedValue, String> {
-            match self {
-                JavaMethods::Cache { cache } => {
-                    if let Ok(r) =
-                        cache.call_method(class, name, sig, args, return_type, returned_object_id)
-                    {
-                        Ok(r)
-                    } else {
-                        Err(
-                            "error while receiving response from java static method call "
-                                .to_string(),
-                        )
-                    }
-                }
-                _ => Err("No cache initialized in java_method_cache !".to_string()),
-            }
-        }
-        pub fn call_static_method(
-            &'a mut self,
-            class: &str,
-            name: &str,
-            sig: &str,
-            args: JavaArgs,
-            return_type: ReturnType,
-            returned_object_id: Option<String>, //in case of JObject as returned value -> id is a string
-                                                //to get the object stored
-        ) -> std::result::Result<ReturnedValue, String> {
-            match self {
-                JavaMethods::Cache { cache } => {
-                    let time = std::time::Instant::now();
-                    if let Ok(r) = cache.call_static_method(
-                        class,
-                        name,
-                        sig,
-                        args,
-                        return_type,
-                        returned_object_id,
-                    ) {
-                        Ok(r)
-                    } else {
-                        Err(
-                            "error while receiving response from java static method call "
-                                .to_string(),
-                        )
-                    }
-                }
-                _ => Err("No cache initialized in java_method_cache !".to_string()),
-            }
-        }
-    }
-
-    pub struct JavaMethodCache<'a> {
-        env: jni::JNIEnv<'a>,
-        env_2: jni::JNIEnv<'a>,
-        activity: &'a mut JObject<'a>,
-        java_vm: &'a JavaVM,
-        instanciate_jobjects: JObjectStore<'a>,
-        native_class_finder: NativeClassFinder<'a>,
-        native_class_finder_2: NativeClassFinder<'a>,
+    /// Cached classes, methods and stored objects. It holds only global references and
+    /// ids, so it can be shared between threads ; the calls take the JNIEnv of the
+    /// calling thread. The lists grow after the build, through the register_* methods
+    /// or on the first call of a method or field designated by name.
+    pub struct JavaMethodCache {
+        instanciate_jobjects: Mutex<JObjectStore>,
+        native_class_finder: NativeClassFinder,
         standard_class_pre_list: StandardClassPreList,
-        standard_class_cache: StandardClassCache<'a>,
-        java_methods_list_ref: JavaMethodsListRefs<'a>,
-        static_method_list: JavaStaticMethodsList<'a>,
-        method_list: JavaMethodsList<'a>,
+        standard_class_cache: RwLock<StandardClassCache>,
+        java_methods_list_ref: JavaMethodsListRefs,
+        static_method_list: RwLock<JavaStaticMethodsList>,
+        method_list: RwLock<JavaMethodsList>,
+        constructor_list: RwLock<JavaMethodsList>,
+        java_fields_list_ref: JavaFieldsListRefs,
+        static_field_list: RwLock<JavaFieldsList>,
+        field_list: RwLock<JavaFieldsList>,
+        resolve_on_first_call: bool,
+        /// Reason of the resolutions on first call that failed, by member, so a missing
+        /// member called every frame does not load its class again on each call.
+        failed_resolutions: RwLock<HashMap<String, String>>,
         cache_builded: bool,
-        executor_channel: ExecutorChannel,
     }
 
-    pub struct JavaMethodCacheBuilder<'a> {
-        pub env: Option<jni::JNIEnv<'a>>,
-        pub activity: Option<&'a mut JObject<'a>>,
-        pub java_vm: Option<&'a JavaVM>,
+    pub struct JavaMethodCacheBuilder {
+        pub activity: Option<GlobalRef>,
+        pub class_loader: Option<GlobalRef>,
         pub standard_class_pre_list: StandardClassPreList,
-        pub java_methods_list_ref: JavaMethodsListRefs<'a>,
+        pub java_methods_list_ref: JavaMethodsListRefs,
+        pub java_fields_list_ref: JavaFieldsListRefs,
+        pub resolve_on_first_call: bool,
         pub cache_builded: bool,
     }
 
-    impl<'a> JavaMethodCacheBuilder<'a> {
-        pub fn build(&mut self, build_cb: impl FnOnce(JavaMethodCache<'a>)) {
-            let executor_channel = ExecutorChannel::new();
-            let mut env = mem::replace(&mut self.env, None);
-            let mut activity = mem::replace(&mut self.activity, None);
-            let mut java_vm = mem::replace(&mut self.java_vm, None);
-            let mut env_2 = java_vm.as_mut().unwrap().get_env().unwrap();
-
-            let mut java_methods_list = JavaStaticMethodsList::new();
-            let mut env_3 = java_vm.as_mut().unwrap().get_env().unwrap();
-            let mut env_4 = java_vm.as_mut().unwrap().get_env().unwrap();
-
-            let mut native_class_finder = native_class_loader_construction(
-                env_3,
-                &mut activity.as_mut().expect("No activity attached, build fn"),
-            );
-            let mut native_class_finder_2 = native_class_loader_construction(
-                env_4,
-                &mut activity.as_mut().expect("No activity attached, build fn"),
+    impl JavaMethodCacheBuilder {
+        pub fn new(class_loader: Option<GlobalRef>, activity: Option<GlobalRef>) -> Self {
+            Self {
+                activity,
+                class_loader,
+                standard_class_pre_list: StandardClassPreList::new(),
+                java_methods_list_ref: JavaMethodsListRefs::new(),
+                java_fields_list_ref: JavaFieldsListRefs::new(),
+                resolve_on_first_call: true,
+                cache_builded: false,
+            }
+        }
+
+        pub fn build(&mut self, env: &mut JNIEnv) -> JavaMethodCache {
+            let activity = self.activity.take();
+
+            let native_class_finder = native_class_loader_construction(
+                env,
+                self.class_loader.as_ref(),
+                activity.as_ref(),
             );
 
             let mut j_object_store = JObjectStore::new();
 
-            let activity_raw_ptr: jobject = activity
-                .as_ref()
-                .expect("No activity for raw pointer")
-                .as_raw();
-
-            j_object_store.add_object_with_id("native_activity", unsafe {
-                JObject::from_raw(activity_raw_ptr)
-            });
+            if let Some(activity) = activity {
+                j_object_store.add_object_with_id(&ObjectId::new("native_activity"), activity);
+            }
 
             let mut java_method_cache = JavaMethodCache {
-                env: env.expect("no env attached to JavaMethodCache"),
-                env_2,
-                activity: activity
-                    .expect("no activity instance object ref attached to javaMethodCache"),
-                java_vm: java_vm.expect("no java_vm reference attached to javaMethodCache"),
                 native_class_finder,
-                native_class_finder_2,
                 standard_class_pre_list: std::mem::replace(
                     &mut self.standard_class_pre_list,
                     StandardClassPreList::new(),
                 ),
-                standard_class_cache: StandardClassCache::new(),
-                instanciate_jobjects: j_object_store,
+                standard_class_cache: RwLock::new(StandardClassCache::new()),
+                instanciate_jobjects: Mutex::new(j_object_store),
                 java_methods_list_ref: std::mem::replace(
                     &mut self.java_methods_list_ref,
                     JavaMethodsListRefs::new(),
                 ),
-                static_method_list: JavaStaticMethodsList::new(),
-                method_list: JavaMethodsList::new(),
+                static_method_list: RwLock::new(JavaStaticMethodsList::new()),
+                method_list: RwLock::new(JavaMethodsList::new()),
+                constructor_list: RwLock::new(JavaMethodsList::with_method_type(
+                    MethodType::Constructor,
+                )),
+                java_fields_list_ref: std::mem::replace(
+                    &mut self.java_fields_list_ref,
+                    JavaFieldsListRefs::new(),
+                ),
+                static_field_list: RwLock::new(JavaFieldsList::new(FieldType::Static)),
+                field_list: RwLock::new(JavaFieldsList::new(FieldType::NonStatic)),
+                resolve_on_first_call: self.resolve_on_first_call,
+                failed_resolutions: RwLock::new(HashMap::new()),
                 cache_builded: false,
-                executor_channel,
             };
+            java_method_cache.build_cache(env);
+            self.cache_builded = true;
 
-            build_cb(java_method_cache);
+            java_method_cache
         }
 
         pub fn add_standard_class_name(&mut self, class_name: &str) -> &mut Self {
@@ -214,24 +146,110 @@ pub mod java_method_build_tools {
             class: &str,
             method_name: &str,
             signature: &str,
-        ) -> &mut Self {
-            self.java_methods_list_ref.add(
-                &self
-                    .env
-                    .as_ref()
-                    .expect("A JNIEnv has to be attached first"),
-                method_type,
+        ) -> MethodHandle {
+            self.java_methods_list_ref
+                .add(method_type, class, method_name, signature)
+        }
+
+        pub fn add_static_method<Args: JavaArgList, R: JavaReturn>(
+            &mut self,
+            class: &str,
+            method_name: &str,
+            signature: &str,
+        ) -> StaticMethod<Args, R> {
+            check_typed_signature::<Args, R>(class, method_name, signature)
+                .unwrap_or_else(|e| panic!("{}", e));
+            StaticMethod::new(self.add_java_method(
+                MethodType::Static,
                 class,
                 method_name,
                 signature,
-            );
-            self
+            ))
+        }
+
+        pub fn add_method<Args: JavaArgList, R: JavaReturn>(
+            &mut self,
+            class: &str,
+            method_name: &str,
+            signature: &str,
+        ) -> Method<Args, R> {
+            check_typed_signature::<Args, R>(class, method_name, signature)
+                .unwrap_or_else(|e| panic!("{}", e));
+            Method::new(self.add_java_method(MethodType::NonStatic, class, method_name, signature))
+        }
+
+        pub fn add_java_field(
+            &mut self,
+            field_type: FieldType,
+            class: &str,
+            field_name: &str,
+            signature: &str,
+        ) -> FieldHandle {
+            self.java_fields_list_ref
+                .add(field_type, class, field_name, signature)
+        }
+
+        pub fn add_static_field<T: JavaReturn>(
+            &mut self,
+            class: &str,
+            field_name: &str,
+            signature: &str,
+        ) -> StaticField<T> {
+            check_typed_field::<T>(class, field_name, signature)
+                .unwrap_or_else(|e| panic!("{}", e));
+            StaticField::new(self.add_java_field(FieldType::Static, class, field_name, signature))
+        }
+
+        pub fn add_field<T: JavaReturn>(
+            &mut self,
+            class: &str,
+            field_name: &str,
+            signature: &str,
+        ) -> Field<T> {
+            check_typed_field::<T>(class, field_name, signature)
+                .unwrap_or_else(|e| panic!("{}", e));
+            Field::new(self.add_java_field(FieldType::NonStatic, class, field_name, signature))
         }
     }
-    impl<'a: 'static> JavaMethodCache<'a> {
+    impl JavaMethodCache {
+        pub fn build_cache(&mut self, env: &mut JNIEnv) {
+            if !self.cache_builded {
+                self.standard_class_cache
+                    .get_mut()
+                    .build_standard_class_list(env, &self.standard_class_pre_list);
+                self.static_method_list.get_mut().build_list_with_ref(
+                    env,
+                    &self.java_methods_list_ref,
+                    &self.native_class_finder,
+                );
+                self.method_list.get_mut().build_list_with_ref(
+                    env,
+                    &self.java_methods_list_ref,
+                    &self.native_class_finder,
+                );
+                self.constructor_list.get_mut().build_list_with_ref(
+                    env,
+                    &self.java_methods_list_ref,
+                    &self.native_class_finder,
+                );
+                self.static_field_list.get_mut().build_list_with_ref(
+                    env,
+                    &self.java_fields_list_ref,
+                    &self.native_class_finder,
+                );
+                self.field_list.get_mut().build_list_with_ref(
+                    env,
+                    &self.java_fields_list_ref,
+                    &self.native_class_finder,
+                );
+                self.cache_builded = true;
+                println!("JavaMethodCache is correctly initialized !");
+            }
+        }
+
         pub fn print_method_list(&self) {
             println!("List of the cached java methods :");
-            for method in self.method_list.methods_list() {
+            for method in self.method_list.read().methods_list() {
                 println!(
                     "- {:?}- [{:?}]- [{:?}]",
                     method.method_name(),
@@ -241,173 +259,691 @@ pub mod java_method_build_tools {
             }
         }
 
-        pub fn set_to_java_methods(self) -> JavaMethods<'a> {
-            JavaMethods::Cache { cache: self }
+        pub fn find_object(&self, id: &ObjectId) -> Option<GlobalRef> {
+            self.instanciate_jobjects.lock().find(id).cloned()
+        }
+
+        pub fn replace_object(&self, id: &ObjectId, object: GlobalRef) -> Option<GlobalRef> {
+            self.instanciate_jobjects.lock().replace(id, object)
+        }
+
+        pub fn remove_object(&self, id: &ObjectId) -> Option<GlobalRef> {
+            self.instanciate_jobjects.lock().remove(id)
+        }
+
+        pub fn object_ids(&self) -> Vec<ObjectId> {
+            self.instanciate_jobjects.lock().ids()
+        }
+
+        pub fn object_count(&self) -> usize {
+            self.instanciate_jobjects.lock().len()
+        }
+
+        pub fn clear_objects(&self) {
+            // the references are deleted once the lock is released
+            let objects = mem::replace(&mut *self.instanciate_jobjects.lock(), JObjectStore::new());
+            drop(objects);
+        }
+
+        /// Creates an instance with a cached constructor and stores it under object_id,
+        /// or under a minted id when None.
+        pub fn new_object(
+            &self,
+            env: &mut JNIEnv,
+            method: &JavaMethodRef,
+            args: JavaArgs,
+            object_id: Option<ObjectId>,
+        ) -> std::result::Result<ObjectId, JniCacheError> {
+            let find_method = self.find_method(env, MethodType::Constructor, method)?;
+
+            let args = args.to_jvalue(
+                env,
+                &self.instanciate_jobjects,
+                &self.native_class_finder,
+                find_method.type_signature(),
+                find_method.method_signature(),
+            )?;
+
+            let result = unsafe {
+                env.new_object_unchecked(
+                    find_method.instance_ref(),
+                    *find_method.method_id(),
+                    args.values(),
+                )
+            };
+            args.delete_locals(env);
+            check_exception(env)?;
+            let object: JObject = result?;
+
+            let object_ref = env.new_global_ref(&object)?;
+            env.delete_local_ref(object)?;
+            let mut objects = self.instanciate_jobjects.lock();
+            Ok(match object_id {
+                Some(id) => {
+                    objects.add_object_with_id(&id, object_ref);
+                    id
+                }
+                None => objects.add_object(object_ref),
+            })
         }
+
         pub fn call_static_method(
-            &'a mut self,
-            class: &str,
-            method_name: &str,
-            sig: &str,
+            &self,
+            env: &mut JNIEnv,
+            method: &JavaMethodRef,
             args: JavaArgs,
-            return_type: ReturnType,
-            object_id: Option<String>,
-        ) -> std::result::Result<ReturnedValue, ()> {
-            let now = std::time::Instant::now();
-            let find_method = self.static_method_list.list_as_mut().iter_mut().find(|m| {
-                m.method_class() == class
-                    && m.method_name() == method_name
-                    && m.method_signature() == sig
-            });
+            object_id: Option<ObjectId>,
+        ) -> std::result::Result<ReturnedValue, JniCacheError> {
+            let find_method = self.find_static_method(env, method)?;
+
+            let args = args.to_jvalue(
+                env,
+                &self.instanciate_jobjects,
+                &self.native_class_finder,
+                find_method.type_signature(),
+                find_method.method_signature(),
+            )?;
+
+            let result = unsafe {
+                env.call_static_method_unchecked(
+                    find_method.instance_ref(),
+                    find_method.method_id(),
+                    find_method.return_type(),
+                    args.values(),
+                )
+            };
+            args.delete_locals(env);
+            check_exception(env)?;
+            let result: JValueOwned = result?;
+
+            ReturnedValue::get_result_type(
+                env,
+                result,
+                &self.standard_class_cache.read(),
+                object_id,
+                &self.instanciate_jobjects,
+            )
+        }
+        pub fn call_method(
+            &self,
+            env: &mut JNIEnv,
+            receiver_id: &ObjectId, //id of the object stored in the JObjectStore the method is called on
+            method: &JavaMethodRef,
+            args: JavaArgs,
+            object_id: Option<ObjectId>, //in case of JObject as returned value -> id to store it with,
+                                         //minted by the store when None
+        ) -> std::result::Result<ReturnedValue, JniCacheError> {
+            let find_method = self.find_method(env, MethodType::NonStatic, method)?;
+
+            let receiver: GlobalRef = self
+                .instanciate_jobjects
+                .lock()
+                .find(receiver_id)
+                .cloned()
+                .ok_or_else(|| JniCacheError::ObjectNotFound(receiver_id.clone()))?;
+
+            let is_receiver_instance = env.is_instance_of(&receiver, find_method.instance_ref())?;
+            if !is_receiver_instance {
+                return Err(JniCacheError::WrongReceiverClass {
+                    object_id: receiver_id.clone(),
+                    class: find_method.method_class().to_string(),
+                });
+            }
 
-            let mut res: Option<JValueOwned> = None;
-
-            if let Some(find_method) = find_method {
-                if let Some(args) = args.to_jvalue(&self.instanciate_jobjects) {
-                    let call_time = std::time::Instant::now();
-                    unsafe {
-                        let result: JValueOwned = self
-                            .env
-                            .call_static_method_unchecked(
-                                JClass::from_raw(find_method.instance_ref().as_raw()),
-                                find_method.method_id(),
-                                return_type,
-                                &args[..],
-                            )
-                            .unwrap_or_else(|_| {
-                                panic!("Error in calling static method [{}]", method_name);
-                            });
-
-                        println!("l [{:?}]", result);
-
-                        res = Some(result);
-                    }
-                } else {
-                    let now = std::time::Instant::now();
-                    unsafe {
-                        let result: JValueOwned = self
-                            .env
-                            .call_static_method_unchecked(
-                                JClass::from_raw(find_method.instance_ref().as_raw()),
-                                find_method.method_id(),
-                                return_type,
-                                &[],
-                            )
-                            .unwrap_or_else(|e| {
-                                panic!(
-                                    "Error in calling static method [{}]\n [{:?}]",
-                                    method_name, e
-                                );
-                            });
-                        res = Some(result);
-                    }
-                };
+            let args = args.to_jvalue(
+                env,
+                &self.instanciate_jobjects,
+                &self.native_class_finder,
+                find_method.type_signature(),
+                find_method.method_signature(),
+            )?;
+
+            let result = unsafe {
+                env.call_method_unchecked(
+                    &receiver,
+                    find_method.method_id(),
+                    find_method.return_type(),
+                    args.values(),
+                )
+            };
+            args.delete_locals(env);
+            check_exception(env)?;
+            let result: JValueOwned = result?;
+
+            ReturnedValue::get_result_type(
+                env,
+                result,
+                &self.standard_class_cache.read(),
+                object_id,
+                &self.instanciate_jobjects,
+            )
+        }
+    }
+
+    impl JavaMethodCache {
+        pub fn get_static_field(
+            &self,
+            env: &mut JNIEnv,
+            field: &JavaFieldRef,
+            object_id: Option<ObjectId>,
+        ) -> std::result::Result<ReturnedValue, JniCacheError> {
+            let find_field = self.find_field(env, FieldType::Static, field)?;
+            let FieldId::Static(field_id) = find_field.field_id() else {
+                return Err(JniCacheError::FieldNotRegistered {
+                    class: find_field.field_class().to_string(),
+                    name: find_field.field_name().to_string(),
+                    sig: find_field.field_signature().to_string(),
+                });
             };
 
-            if let Some(result) = res {
-                let result = Ok(ReturnedValue::get_result_type(
-                    &mut self.env,
-                    result,
-                    &self.standard_class_cache,
-                    object_id,
-                    &mut self.instanciate_jobjects,
-                ));
-                result
-            } else {
-                Err(())
+            let result = env.get_static_field_unchecked(
+                find_field.class_ref(),
+                field_id,
+                find_field.java_type().clone(),
+            );
+            check_exception(env)?;
+            let result: JValueOwned = result?;
+
+            ReturnedValue::get_result_type(
+                env,
+                result,
+                &self.standard_class_cache.read(),
+                object_id,
+                &self.instanciate_jobjects,
+            )
+        }
+
+        pub fn set_static_field(
+            &self,
+            env: &mut JNIEnv,
+            field: &JavaFieldRef,
+            value: JavaArgs,
+        ) -> std::result::Result<(), JniCacheError> {
+            let find_field = self.find_field(env, FieldType::Static, field)?;
+            let FieldId::Static(field_id) = find_field.field_id() else {
+                return Err(JniCacheError::FieldNotRegistered {
+                    class: find_field.field_class().to_string(),
+                    name: find_field.field_name().to_string(),
+                    sig: find_field.field_signature().to_string(),
+                });
+            };
+
+            let value = value.to_field_value(
+                env,
+                &self.instanciate_jobjects,
+                &self.native_class_finder,
+                find_field.java_type(),
+                find_field.field_signature(),
+            )?;
+            let result = env.set_static_field(find_field.class_ref(), field_id, value.borrow());
+            check_exception(env)?;
+            Ok(result?)
+        }
+
+        pub fn get_field(
+            &self,
+            env: &mut JNIEnv,
+            receiver_id: &ObjectId, //id of the stored object the field is read from
+            field: &JavaFieldRef,
+            object_id: Option<ObjectId>,
+        ) -> std::result::Result<ReturnedValue, JniCacheError> {
+            let find_field = self.find_field(env, FieldType::NonStatic, field)?;
+            let FieldId::NonStatic(field_id) = find_field.field_id() else {
+                return Err(JniCacheError::FieldNotRegistered {
+                    class: find_field.field_class().to_string(),
+                    name: find_field.field_name().to_string(),
+                    sig: find_field.field_signature().to_string(),
+                });
+            };
+            let receiver = self.field_receiver(
+                env,
+                receiver_id,
+                find_field.class_ref(),
+                find_field.field_class(),
+            )?;
+
+            let result = env.get_field_unchecked(
+                &receiver,
+                field_id,
+                field_return_type(find_field.java_type()),
+            );
+            check_exception(env)?;
+            let result: JValueOwned = result?;
+
+            ReturnedValue::get_result_type(
+                env,
+                result,
+                &self.standard_class_cache.read(),
+                object_id,
+                &self.instanciate_jobjects,
+            )
+        }
+
+        pub fn set_field(
+            &self,
+            env: &mut JNIEnv,
+            receiver_id: &ObjectId, //id of the stored object the field is written to
+            field: &JavaFieldRef,
+            value: JavaArgs,
+        ) -> std::result::Result<(), JniCacheError> {
+            let find_field = self.find_field(env, FieldType::NonStatic, field)?;
+            let FieldId::NonStatic(field_id) = find_field.field_id() else {
+                return Err(JniCacheError::FieldNotRegistered {
+                    class: find_field.field_class().to_string(),
+                    name: find_field.field_name().to_string(),
+                    sig: find_field.field_signature().to_string(),
+                });
+            };
+            let receiver = self.field_receiver(
+                env,
+                receiver_id,
+                find_field.class_ref(),
+                find_field.field_class(),
+            )?;
+
+            let value = value.to_field_value(
+                env,
+                &self.instanciate_jobjects,
+                &self.native_class_finder,
+                find_field.java_type(),
+                find_field.field_signature(),
+            )?;
+            let result = env.set_field_unchecked(&receiver, field_id, value.borrow());
+            check_exception(env)?;
+            Ok(result?)
+        }
+
+        /// The stored object receiver_id, checked to be an instance of the field class.
+        fn field_receiver(
+            &self,
+            env: &mut JNIEnv,
+            receiver_id: &ObjectId,
+            class_ref: &GlobalRef,
+            class_name: &str,
+        ) -> std::result::Result<GlobalRef, JniCacheError> {
+            let receiver: GlobalRef = self
+                .instanciate_jobjects
+                .lock()
+                .find(receiver_id)
+                .cloned()
+                .ok_or_else(|| JniCacheError::ObjectNotFound(receiver_id.clone()))?;
+
+            if !env.is_instance_of(&receiver, class_ref)? {
+                return Err(JniCacheError::WrongReceiverClass {
+                    object_id: receiver_id.clone(),
+                    class: class_name.to_string(),
+                });
             }
+            Ok(receiver)
         }
-        pub fn call_method(
-            &'a mut self,
+    }
+
+    /***** Registration on the live cache ******/
+    impl JavaMethodCache {
+        fn method_list_of(&self, method_type: MethodType) -> &RwLock<JavaMethodsList> {
+            match method_type {
+                MethodType::Constructor => &self.constructor_list,
+                _ => &self.method_list,
+            }
+        }
+
+        fn field_list_of(&self, field_type: FieldType) -> &RwLock<JavaFieldsList> {
+            match field_type {
+                FieldType::Static => &self.static_field_list,
+                FieldType::NonStatic => &self.field_list,
+            }
+        }
+
+        /// Resolves a method through the native class finder and adds it to the live
+        /// cache. A method already cached keeps its handle.
+        pub fn register_method(
+            &self,
+            env: &mut JNIEnv,
+            method_type: MethodType,
             class: &str,
             method_name: &str,
-            sig: &str,
-            args: JavaArgs,
-            return_type: ReturnType,
-            object_id: Option<String>,
-        ) -> std::result::Result<ReturnedValue, ()> {
-            let find_method = self.method_list.list_as_mut().iter_mut().find(|m| {
-                m.method_class() == class
-                    && m.method_name() == method_name
-                    && m.method_signature() == sig
-            });
-
-            let mut res: Option<JValueOwned> = None;
-
-            if let Some(find_method) = find_method {
-                if let Some(args) = args.to_jvalue(&self.instanciate_jobjects) {
-                    unsafe {
-                        let result: JValueOwned = self
-                            .env
-                            .call_method_unchecked(
-                                JObject::from_raw(find_method.instance_ref().as_raw()),
-                                find_method.method_id(),
-                                return_type,
-                                &args[..],
-                            )
-                            .unwrap_or_else(|_| {
-                                panic!("Error in calling static method [{}]", method_name);
-                            });
-
-                        res = Some(result);
+            signature: &str,
+        ) -> std::result::Result<MethodHandle, JniCacheError> {
+            let resolution_failed = |reason: String| JniCacheError::ResolutionFailed {
+                member: format!("{}.{}{}", class, method_name, signature),
+                reason,
+            };
+            let type_signature = parse_method_signature(method_type, class, method_name, signature)
+                .map_err(resolution_failed)?;
+
+            let index = if method_type == MethodType::Static {
+                let position =
+                    self.static_method_list
+                        .read()
+                        .position(class, method_name, signature);
+                match position {
+                    Some(i) => i,
+                    None => {
+                        // resolved without the lock : loading a class runs java code
+                        let method = JavaStaticMethod::resolve(
+                            env,
+                            &self.native_class_finder,
+                            class,
+                            method_name,
+                            signature,
+                            type_signature,
+                        )
+                        .map_err(|e| resolution_failed(e.to_string()))?;
+                        self.static_method_list.write().insert(method)
                     }
-                } else {
-                    unsafe {
-                        let result: JValueOwned = self
-                            .env
-                            .call_method_unchecked(
-                                JObject::from_raw(find_method.instance_ref().as_raw()),
-                                find_method.method_id(),
-                                return_type,
-                                &[],
-                            )
-                            .unwrap_or_else(|_| {
-                                panic!("Error in calling static method [{}]", method_name);
-                            });
-                        res = Some(result);
+                }
+            } else {
+                let list = self.method_list_of(method_type);
+                let position = list.read().position(class, method_name, signature);
+                match position {
+                    Some(i) => i,
+                    None => {
+                        let method = JavaMethod::resolve(
+                            env,
+                            &self.native_class_finder,
+                            class,
+                            method_name,
+                            signature,
+                            type_signature,
+                        )
+                        .map_err(|e| resolution_failed(e.to_string()))?;
+                        list.write().insert(method)
                     }
-                };
+                }
             };
+            Ok(MethodHandle::new(method_type, index))
+        }
 
-            if let Some(result) = res {
-                Ok(ReturnedValue::get_result_type(
-                    &mut self.env,
-                    result,
-                    &self.standard_class_cache,
-                    object_id,
-                    &mut self.instanciate_jobjects,
-                ))
-            } else {
-                Err(())
+        /// Resolves a field through the native class finder and adds it to the live
+        /// cache. A field already cached keeps its handle.
+        pub fn register_field(
+            &self,
+            env: &mut JNIEnv,
+            field_type: FieldType,
+            class: &str,
+            field_name: &str,
+            signature: &str,
+        ) -> std::result::Result<FieldHandle, JniCacheError> {
+            let resolution_failed = |reason: String| JniCacheError::ResolutionFailed {
+                member: format!("{}.{}: {}", class, field_name, signature),
+                reason,
+            };
+            let java_type =
+                parse_field_signature(class, field_name, signature).map_err(resolution_failed)?;
+
+            let list = self.field_list_of(field_type);
+            let position = list.read().position(class, field_name, signature);
+            let index = match position {
+                Some(i) => i,
+                None => {
+                    let field = JavaField::resolve(
+                        env,
+                        &self.native_class_finder,
+                        field_type,
+                        class,
+                        field_name,
+                        signature,
+                        java_type,
+                    )
+                    .map_err(|e| resolution_failed(e.to_string()))?;
+                    list.write().insert(field)
+                }
+            };
+            Ok(FieldHandle::new(field_type, index))
+        }
+
+        /// Adds a standard class, whose instances are returned as rust values, to the
+        /// live cache. Only the classes extract_value knows how to convert are accepted.
+        pub fn register_standard_class(
+            &self,
+            env: &mut JNIEnv,
+            class_name: &str,
+        ) -> std::result::Result<(), JniCacheError> {
+            if self.standard_class_cache.read().contains(class_name) {
+                return Ok(());
+            }
+            if !is_extractible(class_name) {
+                return Err(JniCacheError::ResolutionFailed {
+                    member: class_name.to_string(),
+                    reason: "its instances cannot be returned as rust values".to_string(),
+                });
+            }
+            let std_class = StandardClass::find(env, class_name).map_err(|e| {
+                JniCacheError::ResolutionFailed {
+                    member: class_name.to_string(),
+                    reason: e.to_string(),
+                }
+            })?;
+            self.standard_class_cache.write().insert(std_class);
+            Ok(())
+        }
+
+        /// Cached method designated by method, resolved on its first call when it is
+        /// designated by name and resolve_on_first_call is set.
+        fn find_method(
+            &self,
+            env: &mut JNIEnv,
+            method_type: MethodType,
+            method: &JavaMethodRef,
+        ) -> std::result::Result<Arc<JavaMethod>, JniCacheError> {
+            let list = self.method_list_of(method_type);
+            let found = list.read().find(method);
+            match found {
+                Err(JniCacheError::MethodNotRegistered { class, name, sig })
+                    if self.resolve_on_first_call =>
+                {
+                    let handle =
+                        self.resolve_method_on_first_call(env, method_type, class, name, sig)?;
+                    list.read().find(&handle.into())
+                }
+                found => found,
+            }
+        }
+
+        fn find_static_method(
+            &self,
+            env: &mut JNIEnv,
+            method: &JavaMethodRef,
+        ) -> std::result::Result<Arc<JavaStaticMethod>, JniCacheError> {
+            let found = self.static_method_list.read().find(method);
+            match found {
+                Err(JniCacheError::MethodNotRegistered { class, name, sig })
+                    if self.resolve_on_first_call =>
+                {
+                    let handle = self.resolve_method_on_first_call(
+                        env,
+                        MethodType::Static,
+                        class,
+                        name,
+                        sig,
+                    )?;
+                    self.static_method_list.read().find(&handle.into())
+                }
+                found => found,
+            }
+        }
+
+        fn resolve_method_on_first_call(
+            &self,
+            env: &mut JNIEnv,
+            method_type: MethodType,
+            class: String,
+            name: String,
+            sig: String,
+        ) -> std::result::Result<MethodHandle, JniCacheError> {
+            self.resolve_once(format!("{}.{}{}", class, name, sig), || {
+                self.register_method(env, method_type, &class, &name, &sig)
+            })
+        }
+
+        /// Runs the resolution of member unless it already failed, in which case the error
+        /// of its first attempt is returned again. Registering the member explicitly is
+        /// still attempted, and once cached it is found before coming here.
+        fn resolve_once<T>(
+            &self,
+            member: String,
+            resolve: impl FnOnce() -> std::result::Result<T, JniCacheError>,
+        ) -> std::result::Result<T, JniCacheError> {
+            if let Some(reason) = self.failed_resolutions.read().get(&member) {
+                return Err(JniCacheError::ResolutionFailed {
+                    member,
+                    reason: reason.clone(),
+                });
+            }
+            resolve().inspect_err(|e| {
+                if let JniCacheError::ResolutionFailed { reason, .. } = e {
+                    self.failed_resolutions
+                        .write()
+                        .insert(member, reason.clone());
+                }
+            })
+        }
+
+        fn find_field(
+            &self,
+            env: &mut JNIEnv,
+            field_type: FieldType,
+            field: &JavaFieldRef,
+        ) -> std::result::Result<Arc<JavaField>, JniCacheError> {
+            let list = self.field_list_of(field_type);
+            let found = list.read().find(field);
+            match found {
+                Err(JniCacheError::FieldNotRegistered { class, name, sig })
+                    if self.resolve_on_first_call =>
+                {
+                    let member = format!("{}.{}: {}", class, name, sig);
+                    let handle = self.resolve_once(member, || {
+                        self.register_field(env, field_type, &class, &name, &sig)
+                    })?;
+                    list.read().find(&handle.into())
+                }
+                found => found,
             }
         }
     }
 
-    fn native_class_loader_construction<'a>(
-        mut env: jni::JNIEnv<'a>,
-        activity: &mut JObject,
-    ) -> NativeClassFinder<'a> {
-        let class_loader_name = "java/lang/ClassLoader";
-        let na_activity_class_name = "android/app/NativeActivity";
+    /// Checks the value type of the field against its signature.
+    pub(crate) fn check_typed_field<T: JavaReturn>(
+        class: &str,
+        field_name: &str,
+        signature: &str,
+    ) -> std::result::Result<(), JniCacheError> {
+        let java_type =
+            JavaType::from_str(signature).map_err(|e| JniCacheError::ResolutionFailed {
+                member: format!("{}.{}: {}", class, field_name, signature),
+                reason: format!("invalid field signature: {}", e),
+            })?;
+        if !is_typed_as(&java_type, &T::java_type()) {
+            return Err(JniCacheError::ReturnTypeMismatch {
+                expected: std::any::type_name::<T>(),
+                found: java_type.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks every argument type and the return type, array elements included, against
+    /// the signature, with the same rules as the arguments given to an untyped call.
+    pub(crate) fn check_typed_signature<Args: JavaArgList, R: JavaReturn>(
+        class: &str,
+        method_name: &str,
+        signature: &str,
+    ) -> std::result::Result<(), JniCacheError> {
+        let (args, ret) =
+            parse_typed_signature(signature).map_err(|e| JniCacheError::ResolutionFailed {
+                member: format!("{}.{}{}", class, method_name, signature),
+                reason: format!("invalid method signature: {}", e),
+            })?;
+        let arg_types = Args::java_types();
+        if args.len() != arg_types.len() {
+            return Err(JniCacheError::ArgumentMismatch {
+                sig: signature.to_string(),
+                message: format!(
+                    "expected {} argument(s), typed with {}",
+                    args.len(),
+                    arg_types.len()
+                ),
+            });
+        }
+        for (position, (declared, rust_type)) in args.iter().zip(arg_types.iter()).enumerate() {
+            if !is_passable_as(declared, rust_type) {
+                return Err(JniCacheError::ArgumentMismatch {
+                    sig: signature.to_string(),
+                    message: format!(
+                        "argument {} is a [{}], it cannot be typed as [{}]",
+                        position, declared, rust_type
+                    ),
+                });
+            }
+        }
+        if !is_typed_as(&ret, &R::java_type()) {
+            return Err(JniCacheError::ReturnTypeMismatch {
+                expected: std::any::type_name::<R>(),
+                found: ret.to_string(),
+            });
+        }
+        Ok(())
+    }
 
-        let na_class: jni::objects::JClass = env
-            .find_class(na_activity_class_name)
-            .unwrap_or_else(|_| panic!("No NativeActivity class name found !"));
+    /// Argument and return types of a method signature ; the return type of TypeSignature
+    /// drops the class of objects and the element type of arrays.
+    fn parse_typed_signature(
+        signature: &str,
+    ) -> std::result::Result<(Vec<JavaType>, JavaType), jni::errors::Error> {
+        let type_signature = TypeSignature::from_str(signature)?;
+        // a parsed method signature has a closing parenthesis
+        let ret = match signature.rsplit_once(')').map_or("", |(_, ret)| ret) {
+            "V" => JavaType::Primitive(Primitive::Void),
+            ret => JavaType::from_str(ret)?,
+        };
+        Ok((type_signature.args, ret))
+    }
 
-        let class_loader_method: jni::objects::JMethodID = env
-            .get_method_id(na_class, "getClassLoader", "()Ljava/lang/ClassLoader;")
-            .unwrap_or_else(|_| panic!("didn't find getClassLoader Method !"));
+    /// The class loader used to find the custom classes is, in order of preference :
+    /// the one given at init, the one of the android activity, or the system class loader.
+    fn native_class_loader_construction(
+        env: &mut JNIEnv,
+        class_loader: Option<&GlobalRef>,
+        activity: Option<&GlobalRef>,
+    ) -> NativeClassFinder {
+        let class_loader_name = "java/lang/ClassLoader";
+        let na_activity_class_name = "android/app/NativeActivity";
 
-        let class_loader_instance: jni::objects::JObject = unsafe {
-            env.call_method_unchecked(
-                &activity,
-                class_loader_method,
-                jni::signature::ReturnType::Object,
-                &[],
-            )
-            .unwrap_or_else(|_| panic!("cannot call the method class loader on activity !"))
-            .l()
-            .unwrap()
+        let class_loader_instance: jni::objects::JObject = match (class_loader, activity) {
+            (Some(class_loader), _) => env
+                .new_local_ref(class_loader)
+                .unwrap_or_else(|_| panic!("cannot create a local ref of the class loader !")),
+            (None, Some(activity)) => {
+                let na_class: jni::objects::JClass = env
+                    .find_class(na_activity_class_name)
+                    .unwrap_or_else(|_| panic!("No NativeActivity class name found !"));
+
+                let class_loader_method: jni::objects::JMethodID = env
+                    .get_method_id(na_class, "getClassLoader", "()Ljava/lang/ClassLoader;")
+                    .unwrap_or_else(|_| panic!("didn't find getClassLoader Method !"));
+
+                unsafe {
+                    env.call_method_unchecked(
+                        activity,
+                        class_loader_method,
+                        jni::signature::ReturnType::Object,
+                        &[],
+                    )
+                    .unwrap_or_else(|_| panic!("cannot call the method class loader on activity !"))
+                    .l()
+                    .unwrap()
+                }
+            }
+            (None, None) => env
+                .call_static_method(
+                    class_loader_name,
+                    "getSystemClassLoader",
+                    "()Ljava/lang/ClassLoader;",
+                    &[],
+                )
+                .unwrap_or_else(|_| panic!("cannot get the system class loader !"))
+                .l()
+                .unwrap(),
         };
 
         let class_loader_class = env
@@ -422,49 +958,212 @@ pub mod java_method_build_tools {
             )
             .unwrap_or_else(|_| panic!("didn't find loadClass Method in class_loader class !"));
 
-        NativeClassFinder::new(env, class_loader_instance, find_class)
+        let class_loader_ref = env
+            .new_global_ref(&class_loader_instance)
+            .unwrap_or_else(|_| panic!("cannot create a global ref of the class loader !"));
+        let _ = env.delete_local_ref(class_loader_instance);
+
+        NativeClassFinder::new(class_loader_ref, find_class)
     }
 
     mod native_class_finder {
 
+        use std::collections::HashMap;
+
         use super::*;
 
-        pub struct NativeClassFinder<'a> {
-            pub env: jni::JNIEnv<'a>,
-            pub class_loader: jni::objects::JObject<'a>,
+        /// Loads the classes through the class loader. The classes of the reference types
+        /// the stored objects are checked against are kept, by type descriptor.
+        pub struct NativeClassFinder {
+            pub class_loader: GlobalRef,
             pub find_class_method: JMethodID,
+            type_classes: RwLock<HashMap<String, GlobalRef>>,
         }
 
-        impl<'a> NativeClassFinder<'a> {
-            pub fn new(
-                env: jni::JNIEnv<'a>,
-                class_loader: jni::objects::JObject<'a>,
-                find_class_method: JMethodID,
-            ) -> Self {
+        impl NativeClassFinder {
+            pub fn new(class_loader: GlobalRef, find_class_method: JMethodID) -> Self {
                 Self {
-                    env,
                     class_loader,
                     find_class_method,
+                    type_classes: RwLock::new(HashMap::new()),
                 }
             }
-        }
-    }
-
-    pub mod j_object_ref {
-
-        use jni::JNIEnv;
 
-        use super::*;
+            /// True when object can be given where java_type, an object or array type, is
+            /// expected. The class of java_type is loaded on its first check.
+            pub fn is_instance_of(
+                &self,
+                env: &mut JNIEnv,
+                object: &JObject,
+                java_type: &JavaType,
+            ) -> std::result::Result<bool, JniCacheError> {
+                let descriptor = java_type.to_string();
+                let found = self.type_classes.read().get(&descriptor).cloned();
+                let class = match found {
+                    Some(class) => class,
+                    None => {
+                        let class = self.load_type_class(env, java_type)?;
+                        let class_ref = env.new_global_ref(&class)?;
+                        env.delete_local_ref(class)?;
+                        self.type_classes
+                            .write()
+                            .entry(descriptor)
+                            .or_insert(class_ref)
+                            .clone()
+                    }
+                };
+                Ok(env.is_instance_of(object, &class)?)
+            }
 
-        pub struct JavaMethod<'a> {
-            instance_ref: JObject<'a>,
-            method_class: String,
+            /// The class of an array of objects is the one of an empty array of its
+            /// elements, so that arrays of custom classes go through the class loader too.
+            fn load_type_class<'local>(
+                &self,
+                env: &mut JNIEnv<'local>,
+                java_type: &JavaType,
+            ) -> std::result::Result<JClass<'local>, JniCacheError> {
+                match java_type {
+                    JavaType::Object(class_name) => self.load_class(env, class_name),
+                    JavaType::Array(element) => match element.as_ref() {
+                        JavaType::Primitive(_) => {
+                            let class = env.find_class(java_type.to_string());
+                            check_exception(env)?;
+                            Ok(class?)
+                        }
+                        element => {
+                            let element_class = self.load_type_class(env, element)?;
+                            let array = env.new_object_array(0, &element_class, JObject::null());
+                            check_exception(env)?;
+                            let array = array?;
+                            let class = env.get_object_class(&array)?;
+                            env.delete_local_ref(array)?;
+                            env.delete_local_ref(element_class)?;
+                            Ok(class)
+                        }
+                    },
+                    other => Err(JniCacheError::ArgumentConversion(format!(
+                        "[{}] is not an object type",
+                        other
+                    ))),
+                }
+            }
+
+            /// Loads a class through the class loader, from its "com/example/Foo" name.
+            /// A ClassNotFoundException is cleared and returned as the error.
+            pub fn load_class<'local>(
+                &self,
+                env: &mut JNIEnv<'local>,
+                class_name: &str,
+            ) -> std::result::Result<JClass<'local>, JniCacheError> {
+                // ClassLoader.loadClass expects a binary name : "com.example.Foo"
+                let binary_name: JObject = env.new_string(class_name.replace('/', "."))?.into();
+                let class = unsafe {
+                    env.call_method_unchecked(
+                        &self.class_loader,
+                        self.find_class_method,
+                        ReturnType::Object,
+                        &[JValue::Object(&binary_name).as_jni()],
+                    )
+                };
+                env.delete_local_ref(binary_name)?;
+                check_exception(env)?;
+                Ok(class?.l()?.into())
+            }
+        }
+    }
+
+    pub mod j_object_ref {
+
+        use std::collections::HashMap;
+        use std::sync::Arc;
+
+        use jni::JNIEnv;
+
+        use super::*;
+
+        /// Index of the cached methods by (class, name, signature).
+        /// Nested maps allow a lookup with borrowed strs, without building an owned key.
+        #[derive(Default)]
+        pub struct MethodIndex {
+            inner: HashMap<String, HashMap<String, HashMap<String, usize>>>,
+        }
+
+        impl MethodIndex {
+            pub fn insert(&mut self, class: &str, name: &str, sig: &str, index: usize) {
+                self.inner
+                    .entry(class.to_string())
+                    .or_default()
+                    .entry(name.to_string())
+                    .or_default()
+                    .insert(sig.to_string(), index);
+            }
+
+            pub fn get(&self, class: &str, name: &str, sig: &str) -> Option<usize> {
+                self.inner.get(class)?.get(name)?.get(sig).copied()
+            }
+        }
+
+        fn resolve_index(
+            index: &MethodIndex,
+            len: usize,
+            method: &JavaMethodRef,
+            method_type: MethodType,
+        ) -> std::result::Result<usize, JniCacheError> {
+            match method {
+                JavaMethodRef::Handle(handle) => {
+                    if handle.method_type() == method_type && handle.index() < len {
+                        Ok(handle.index())
+                    } else {
+                        Err(JniCacheError::InvalidMethodHandle(*handle))
+                    }
+                }
+                JavaMethodRef::Name { class, name, sig } => {
+                    index
+                        .get(class, name, sig)
+                        .ok_or_else(|| JniCacheError::MethodNotRegistered {
+                            class: class.to_string(),
+                            name: name.to_string(),
+                            sig: sig.to_string(),
+                        })
+                }
+            }
+        }
+
+        /// Parses the signature of a method, a constructor has to be a void "<init>".
+        pub fn parse_method_signature(
+            method_type: MethodType,
+            class_name: &str,
+            method_name: &str,
+            signature: &str,
+        ) -> std::result::Result<TypeSignature, String> {
+            let type_signature = TypeSignature::from_str(signature).map_err(|e| {
+                format!(
+                    "invalid signature [{}] for [{}.{}] : [{:?}]",
+                    signature, class_name, method_name, e
+                )
+            })?;
+            if method_type == MethodType::Constructor
+                && (method_name != "<init>"
+                    || type_signature.ret != ReturnType::Primitive(Primitive::Void))
+            {
+                return Err(format!(
+                    "[{}.{}{}] is not a constructor, expected <init> with a void return",
+                    class_name, method_name, signature
+                ));
+            }
+            Ok(type_signature)
+        }
+
+        pub struct JavaMethod {
+            instance_ref: GlobalRef,
+            method_class: String,
             method_name: String,
             method_signature: String,
+            type_signature: TypeSignature,
             method_id: JMethodID,
         }
-        impl<'a> JavaMethod<'a> {
-            pub fn instance_ref(&'a self) -> &'a JObject<'a> {
+        impl JavaMethod {
+            pub fn instance_ref(&self) -> &GlobalRef {
                 &self.instance_ref
             }
 
@@ -480,111 +1179,160 @@ pub mod java_method_build_tools {
             pub fn method_signature(&self) -> &str {
                 self.method_signature.as_str()
             }
+            pub fn type_signature(&self) -> &TypeSignature {
+                &self.type_signature
+            }
+            pub fn return_type(&self) -> ReturnType {
+                self.type_signature.ret.clone()
+            }
             pub fn new(
-                class_instance: JObject<'a>,
+                class_instance: GlobalRef,
                 method_class: &str,
                 method_name: &str,
                 signature: &str,
+                type_signature: TypeSignature,
                 method_id: JMethodID,
-            ) -> JavaMethod<'a> {
+            ) -> JavaMethod {
                 Self {
                     instance_ref: class_instance,
                     method_class: method_class.to_owned(),
                     method_name: method_name.to_owned(),
                     method_signature: signature.to_owned(),
+                    type_signature,
                     method_id,
                 }
             }
+
+            /// Loads the class through the class finder and looks up the method id.
+            pub fn resolve(
+                env: &mut JNIEnv,
+                native_class_finder: &NativeClassFinder,
+                class_name: &str,
+                method_name: &str,
+                signature: &str,
+                type_signature: TypeSignature,
+            ) -> std::result::Result<JavaMethod, JniCacheError> {
+                let class: JClass = native_class_finder.load_class(env, class_name)?;
+                let method_id = env.get_method_id(&class, method_name, signature);
+                check_exception(env)?;
+                let method_id: JMethodID = method_id?;
+                let instance_ref: GlobalRef = env.new_global_ref(&class)?;
+                env.delete_local_ref(class)?;
+                Ok(JavaMethod::new(
+                    instance_ref,
+                    class_name,
+                    method_name,
+                    signature,
+                    type_signature,
+                    method_id,
+                ))
+            }
         }
 
-        pub struct JavaMethodsList<'a> {
-            methods_list: Vec<JavaMethod<'a>>,
+        /// Instance methods, or constructors : both are called with a JMethodID.
+        pub struct JavaMethodsList {
+            method_type: MethodType,
+            methods_list: Vec<Arc<JavaMethod>>,
+            index: MethodIndex,
         }
 
-        impl<'a> JavaMethodsList<'a> {
+        impl JavaMethodsList {
             pub fn new() -> Self {
+                Self::with_method_type(MethodType::NonStatic)
+            }
+            pub fn with_method_type(method_type: MethodType) -> Self {
                 Self {
+                    method_type,
                     methods_list: vec![],
+                    index: MethodIndex::default(),
                 }
             }
-            pub fn methods_list(&self) -> &Vec<JavaMethod<'a>> {
+            pub fn find(
+                &self,
+                method: &JavaMethodRef,
+            ) -> std::result::Result<Arc<JavaMethod>, JniCacheError> {
+                let i = resolve_index(
+                    &self.index,
+                    self.methods_list.len(),
+                    method,
+                    self.method_type,
+                )?;
+                Ok(self.methods_list[i].clone())
+            }
+            pub fn position(&self, class: &str, name: &str, sig: &str) -> Option<usize> {
+                self.index.get(class, name, sig)
+            }
+            pub fn methods_list(&self) -> &Vec<Arc<JavaMethod>> {
                 &self.methods_list
             }
-            pub fn list_as_mut(&mut self) -> &mut Vec<JavaMethod<'a>> {
-                &mut self.methods_list
+            /// Appends the method, its handle index is its position in the list.
+            pub fn push(&mut self, method: JavaMethod) -> usize {
+                let i = self.methods_list.len();
+                self.index.insert(
+                    method.method_class(),
+                    method.method_name(),
+                    method.method_signature(),
+                    i,
+                );
+                self.methods_list.push(Arc::new(method));
+                i
+            }
+            /// Appends the method unless it is already cached, and gives back its index.
+            pub fn insert(&mut self, method: JavaMethod) -> usize {
+                match self.position(
+                    method.method_class(),
+                    method.method_name(),
+                    method.method_signature(),
+                ) {
+                    Some(i) => i,
+                    None => self.push(method),
+                }
             }
             pub fn build_list_with_ref(
                 &mut self,
                 env: &mut JNIEnv,
-                list_refs: &mut JavaMethodsListRefs,
-                native_class_finder: &'a mut NativeClassFinder,
+                list_refs: &JavaMethodsListRefs,
+                native_class_finder: &NativeClassFinder,
             ) {
-                let env = &mut native_class_finder.env;
-
-                for method_ref in &mut list_refs.methods_list.iter().filter(|item| {
-                    if let MethodType::NonStatic = item.method_type {
-                        true
-                    } else {
-                        false
-                    }
-                }) {
-                    let instance_ref: JObject = unsafe {
-                        env.call_method_unchecked(
-                            &native_class_finder.class_loader,
-                            native_class_finder.find_class_method,
-                            ReturnType::Object,
-                            &[JValue::Object(&method_ref.class).as_jni()],
-                        )
-                        .unwrap_or_else(|_| {
-                            panic!("can't instanciate [{:?}]", method_ref.method_name)
-                        })
-                        .l()
-                        .unwrap()
-                    };
-
-                    let instance_ref_2: JObject = unsafe {
-                        env.call_method_unchecked(
-                            &native_class_finder.class_loader,
-                            native_class_finder.find_class_method,
-                            ReturnType::Object,
-                            &[JValue::Object(&method_ref.class).as_jni()],
-                        )
-                        .unwrap_or_else(|_| {
-                            panic!("can't instanciate [{:?}]", method_ref.method_name)
-                        })
-                        .l()
-                        .unwrap()
-                    };
-                    let method_id: JMethodID = env
-                        .get_method_id(
-                            JClass::from(instance_ref),
-                            method_ref.method_name().as_str(),
-                            method_ref.method_signature().as_str(),
-                        )
-                        .unwrap();
-                    let new_method = JavaMethod::new(
-                        instance_ref_2,
+                let method_type = self.method_type;
+                for method_ref in list_refs
+                    .methods_list
+                    .iter()
+                    .filter(|item| item.method_type == method_type)
+                {
+                    let new_method = JavaMethod::resolve(
+                        env,
+                        native_class_finder,
                         method_ref.class_name.as_str(),
-                        method_ref.method_name().as_str(),
-                        method_ref.method_signature().as_str(),
-                        method_id,
-                    );
-
-                    self.methods_list.push(new_method);
+                        method_ref.method_name.as_str(),
+                        method_ref.method_signature.as_str(),
+                        method_ref.type_signature.clone(),
+                    )
+                    .unwrap_or_else(|e| {
+                        panic!(
+                            "cannot cache [{}.{}{}] : [{}]",
+                            method_ref.class_name,
+                            method_ref.method_name,
+                            method_ref.method_signature,
+                            e
+                        )
+                    });
+                    self.push(new_method);
                 }
             }
         }
         #[derive(Debug)]
-        pub struct JavaStaticMethod<'a> {
-            instance_ref: JObject<'a>,
+        pub struct JavaStaticMethod {
+            instance_ref: GlobalRef,
             method_class: String,
             method_name: String,
             method_signature: String,
+            type_signature: TypeSignature,
             method_id: JStaticMethodID,
         }
 
-        impl<'a> JavaStaticMethod<'a> {
-            pub fn instance_ref(&'a self) -> &'a JObject<'a> {
+        impl JavaStaticMethod {
+            pub fn instance_ref(&self) -> &GlobalRef {
                 &self.instance_ref
             }
 
@@ -600,48 +1348,113 @@ pub mod java_method_build_tools {
             pub fn method_signature(&self) -> &str {
                 self.method_signature.as_str()
             }
+            pub fn type_signature(&self) -> &TypeSignature {
+                &self.type_signature
+            }
+            pub fn return_type(&self) -> ReturnType {
+                self.type_signature.ret.clone()
+            }
             pub fn new(
-                class_instance: JObject<'a>,
+                class_instance: GlobalRef,
                 method_class: &str,
                 method_name: &str,
                 signature: &str,
+                type_signature: TypeSignature,
                 method_id: JStaticMethodID,
-            ) -> JavaStaticMethod<'a> {
+            ) -> JavaStaticMethod {
                 Self {
                     instance_ref: class_instance,
                     method_class: method_class.to_owned(),
                     method_name: method_name.to_owned(),
                     method_signature: signature.to_owned(),
+                    type_signature,
                     method_id,
                 }
             }
+
+            /// Loads the class through the class finder and looks up the static method id.
+            pub fn resolve(
+                env: &mut JNIEnv,
+                native_class_finder: &NativeClassFinder,
+                class_name: &str,
+                method_name: &str,
+                signature: &str,
+                type_signature: TypeSignature,
+            ) -> std::result::Result<JavaStaticMethod, JniCacheError> {
+                let class: JClass = native_class_finder.load_class(env, class_name)?;
+                let method_id = env.get_static_method_id(&class, method_name, signature);
+                check_exception(env)?;
+                let method_id: JStaticMethodID = method_id?;
+                let instance_ref: GlobalRef = env.new_global_ref(&class)?;
+                env.delete_local_ref(class)?;
+                Ok(JavaStaticMethod::new(
+                    instance_ref,
+                    class_name,
+                    method_name,
+                    signature,
+                    type_signature,
+                    method_id,
+                ))
+            }
         }
 
-        pub struct JavaStaticMethodsList<'a> {
-            methods_list: Vec<JavaStaticMethod<'a>>,
+        pub struct JavaStaticMethodsList {
+            methods_list: Vec<Arc<JavaStaticMethod>>,
+            index: MethodIndex,
         }
 
-        impl<'a> JavaStaticMethodsList<'a> {
+        impl JavaStaticMethodsList {
             pub fn new() -> Self {
                 Self {
                     methods_list: vec![],
+                    index: MethodIndex::default(),
                 }
             }
-            pub fn methods_list(&self) -> &Vec<JavaStaticMethod<'a>> {
-                &self.methods_list
+            pub fn find(
+                &self,
+                method: &JavaMethodRef,
+            ) -> std::result::Result<Arc<JavaStaticMethod>, JniCacheError> {
+                let i = resolve_index(
+                    &self.index,
+                    self.methods_list.len(),
+                    method,
+                    MethodType::Static,
+                )?;
+                Ok(self.methods_list[i].clone())
+            }
+            pub fn position(&self, class: &str, name: &str, sig: &str) -> Option<usize> {
+                self.index.get(class, name, sig)
             }
-            pub fn list_as_mut(&mut self) -> &mut Vec<JavaStaticMethod<'a>> {
-                &mut self.methods_list
+            /// Appends the method, its handle index is its position in the list.
+            pub fn push(&mut self, method: JavaStaticMethod) -> usize {
+                let i = self.methods_list.len();
+                self.index.insert(
+                    method.method_class(),
+                    method.method_name(),
+                    method.method_signature(),
+                    i,
+                );
+                self.methods_list.push(Arc::new(method));
+                i
+            }
+            /// Appends the method unless it is already cached, and gives back its index.
+            pub fn insert(&mut self, method: JavaStaticMethod) -> usize {
+                match self.position(
+                    method.method_class(),
+                    method.method_name(),
+                    method.method_signature(),
+                ) {
+                    Some(i) => i,
+                    None => self.push(method),
+                }
             }
             pub fn build_list_with_ref(
                 &mut self,
                 env: &mut JNIEnv,
-                list_refs: &mut JavaMethodsListRefs,
-                native_class_finder: &'a mut NativeClassFinder,
+                list_refs: &JavaMethodsListRefs,
+                native_class_finder: &NativeClassFinder,
             ) {
-                let env = &mut native_class_finder.env;
-
-                for method_ref in &mut list_refs.methods_list.iter().filter(|item| {
+                for method_ref in list_refs.methods_list.iter().filter(|item| {
                     if let MethodType::Static = item.method_type {
                         true
                     } else {
@@ -649,299 +1462,1040 @@ pub mod java_method_build_tools {
                         false
                     }
                 }) {
-                    let instance_ref: JObject = unsafe {
-                        env.call_method_unchecked(
-                            &native_class_finder.class_loader,
-                            native_class_finder.find_class_method,
-                            ReturnType::Object,
-                            &[JValue::Object(&method_ref.class).as_jni()],
-                        )
-                        .unwrap_or_else(|_| {
-                            panic!("can't instanciate [{:?}]", method_ref.method_name)
-                        })
-                        .l()
-                        .unwrap()
-                    };
-
-                    let instance_ref_2: JObject = unsafe {
-                        env.call_method_unchecked(
-                            &native_class_finder.class_loader,
-                            native_class_finder.find_class_method,
-                            ReturnType::Object,
-                            &[JValue::Object(&method_ref.class).as_jni()],
+                    let new_method = JavaStaticMethod::resolve(
+                        env,
+                        native_class_finder,
+                        method_ref.class_name.as_str(),
+                        method_ref.method_name.as_str(),
+                        method_ref.method_signature.as_str(),
+                        method_ref.type_signature.clone(),
+                    )
+                    .unwrap_or_else(|e| {
+                        panic!(
+                            "cannot cache [{}.{}{}] : [{}]",
+                            method_ref.class_name,
+                            method_ref.method_name,
+                            method_ref.method_signature,
+                            e
                         )
-                        .unwrap_or_else(|_| {
-                            panic!("can't instanciate [{:?}]", method_ref.method_name)
-                        })
-                        .l()
-                        .unwrap()
-                    };
-                    let method_id: JStaticMethodID = env
-                        .get_static_method_id(
-                            JClass::from(instance_ref),
-                            method_ref.method_name().as_str(),
-                            method_ref.method_signature().as_str(),
+                    });
+                    self.push(new_method);
+                }
+            }
+        }
+
+        #[derive(Debug)]
+        pub struct JavaMethodsListRefs {
+            methods_list: Vec<MethodItemRef>,
+        }
+
+        impl JavaMethodsListRefs {
+            pub fn new() -> Self {
+                Self {
+                    methods_list: vec![],
+                }
+            }
+
+            pub fn add(
+                &mut self,
+                method_type: MethodType,
+                class_name: &str,
+                method_name: &str,
+                signature: &str,
+            ) -> MethodHandle {
+                let index = self
+                    .methods_list
+                    .iter()
+                    .filter(|item| item.method_type == method_type)
+                    .count();
+                let type_signature =
+                    parse_method_signature(method_type, class_name, method_name, signature)
+                        .unwrap_or_else(|e| panic!("{}", e));
+                let method_name: String = method_name.to_string();
+                let signature: String = signature.to_string();
+
+                let new_method = MethodItemRef::new(
+                    method_type,
+                    class_name.to_owned(),
+                    method_name,
+                    signature,
+                    type_signature,
+                );
+
+                self.methods_list.push(new_method);
+                MethodHandle::new(method_type, index)
+            }
+        }
+
+        #[derive(Debug)]
+        pub struct MethodItemRef {
+            method_type: MethodType,
+            class_name: String,
+            method_name: String,
+            method_signature: String,
+            type_signature: TypeSignature,
+        }
+
+        impl MethodItemRef {
+            pub fn new(
+                method_type: MethodType,
+                class_name: String,
+                method_name: String,
+                method_signature: String,
+                type_signature: TypeSignature,
+            ) -> Self {
+                Self {
+                    method_type,
+                    class_name,
+                    method_name,
+                    method_signature,
+                    type_signature,
+                }
+            }
+        }
+    }
+
+    pub mod j_field_ref {
+
+        use std::collections::HashMap;
+        use std::str::FromStr;
+        use std::sync::Arc;
+
+        use jni::objects::{JFieldID, JStaticFieldID};
+
+        use super::*;
+        use crate::jni_methods_cache::methods_cache::java_method_cache_utils::{
+            FieldHandle, FieldType, JavaFieldRef,
+        };
+
+        #[derive(Clone, Copy)]
+        pub enum FieldId {
+            Static(JStaticFieldID),
+            NonStatic(JFieldID),
+        }
+
+        pub struct JavaField {
+            class_ref: GlobalRef,
+            field_class: String,
+            field_name: String,
+            field_signature: String,
+            java_type: JavaType,
+            field_id: FieldId,
+        }
+
+        impl JavaField {
+            pub fn class_ref(&self) -> &GlobalRef {
+                &self.class_ref
+            }
+            pub fn field_id(&self) -> FieldId {
+                self.field_id
+            }
+            pub fn field_class(&self) -> &str {
+                self.field_class.as_str()
+            }
+            pub fn field_name(&self) -> &str {
+                self.field_name.as_str()
+            }
+            pub fn field_signature(&self) -> &str {
+                self.field_signature.as_str()
+            }
+            pub fn java_type(&self) -> &JavaType {
+                &self.java_type
+            }
+
+            /// Loads the class through the class finder and looks up the field id.
+            pub fn resolve(
+                env: &mut JNIEnv,
+                native_class_finder: &NativeClassFinder,
+                field_type: FieldType,
+                class_name: &str,
+                field_name: &str,
+                signature: &str,
+                java_type: JavaType,
+            ) -> std::result::Result<JavaField, JniCacheError> {
+                let class: JClass = native_class_finder.load_class(env, class_name)?;
+                let field_id = match field_type {
+                    FieldType::Static => env
+                        .get_static_field_id(&class, field_name, signature)
+                        .map(FieldId::Static),
+                    FieldType::NonStatic => env
+                        .get_field_id(&class, field_name, signature)
+                        .map(FieldId::NonStatic),
+                };
+                check_exception(env)?;
+                let field_id = field_id?;
+                let class_ref: GlobalRef = env.new_global_ref(&class)?;
+                env.delete_local_ref(class)?;
+                Ok(JavaField {
+                    class_ref,
+                    field_class: class_name.to_string(),
+                    field_name: field_name.to_string(),
+                    field_signature: signature.to_string(),
+                    java_type,
+                    field_id,
+                })
+            }
+        }
+
+        /// Parses the signature of a field, a method signature is refused.
+        pub fn parse_field_signature(
+            class_name: &str,
+            field_name: &str,
+            signature: &str,
+        ) -> std::result::Result<JavaType, String> {
+            let java_type = JavaType::from_str(signature).map_err(|e| {
+                format!(
+                    "invalid signature [{}] for field [{}.{}] : [{:?}]",
+                    signature, class_name, field_name, e
+                )
+            })?;
+            if let JavaType::Method(_) = java_type {
+                return Err(format!(
+                    "[{}] is a method signature, it cannot type the field [{}.{}]",
+                    signature, class_name, field_name
+                ));
+            }
+            Ok(java_type)
+        }
+
+        /// The ReturnType a field of type java_type is read with.
+        pub fn field_return_type(java_type: &JavaType) -> ReturnType {
+            match java_type {
+                JavaType::Primitive(primitive) => ReturnType::Primitive(*primitive),
+                JavaType::Array(_) => ReturnType::Array,
+                _ => ReturnType::Object,
+            }
+        }
+
+        /// Fields by (class, name, signature), same layout as the method index.
+        #[derive(Default)]
+        struct FieldIndex {
+            inner: HashMap<String, HashMap<String, HashMap<String, usize>>>,
+        }
+
+        impl FieldIndex {
+            fn insert(&mut self, class: &str, name: &str, sig: &str, index: usize) {
+                self.inner
+                    .entry(class.to_string())
+                    .or_default()
+                    .entry(name.to_string())
+                    .or_default()
+                    .insert(sig.to_string(), index);
+            }
+
+            fn get(&self, class: &str, name: &str, sig: &str) -> Option<usize> {
+                self.inner.get(class)?.get(name)?.get(sig).copied()
+            }
+        }
+
+        pub struct JavaFieldsList {
+            field_type: FieldType,
+            fields_list: Vec<Arc<JavaField>>,
+            index: FieldIndex,
+        }
+
+        impl JavaFieldsList {
+            pub fn new(field_type: FieldType) -> Self {
+                Self {
+                    field_type,
+                    fields_list: vec![],
+                    index: FieldIndex::default(),
+                }
+            }
+
+            pub fn find(
+                &self,
+                field: &JavaFieldRef,
+            ) -> std::result::Result<Arc<JavaField>, JniCacheError> {
+                let i = match field {
+                    JavaFieldRef::Handle(handle) => {
+                        if handle.field_type() == self.field_type
+                            && handle.index() < self.fields_list.len()
+                        {
+                            handle.index()
+                        } else {
+                            return Err(JniCacheError::InvalidFieldHandle(*handle));
+                        }
+                    }
+                    JavaFieldRef::Name { class, name, sig } => self
+                        .index
+                        .get(class, name, sig)
+                        .ok_or_else(|| JniCacheError::FieldNotRegistered {
+                            class: class.to_string(),
+                            name: name.to_string(),
+                            sig: sig.to_string(),
+                        })?,
+                };
+                Ok(self.fields_list[i].clone())
+            }
+
+            pub fn position(&self, class: &str, name: &str, sig: &str) -> Option<usize> {
+                self.index.get(class, name, sig)
+            }
+
+            /// Appends the field, its handle index is its position in the list.
+            pub fn push(&mut self, field: JavaField) -> usize {
+                let i = self.fields_list.len();
+                self.index.insert(
+                    field.field_class(),
+                    field.field_name(),
+                    field.field_signature(),
+                    i,
+                );
+                self.fields_list.push(Arc::new(field));
+                i
+            }
+
+            /// Appends the field unless it is already cached, and gives back its index.
+            pub fn insert(&mut self, field: JavaField) -> usize {
+                match self.position(
+                    field.field_class(),
+                    field.field_name(),
+                    field.field_signature(),
+                ) {
+                    Some(i) => i,
+                    None => self.push(field),
+                }
+            }
+
+            pub fn build_list_with_ref(
+                &mut self,
+                env: &mut JNIEnv,
+                list_refs: &JavaFieldsListRefs,
+                native_class_finder: &NativeClassFinder,
+            ) {
+                let field_type = self.field_type;
+                for field_ref in list_refs
+                    .fields_list
+                    .iter()
+                    .filter(|item| item.field_type == field_type)
+                {
+                    let new_field = JavaField::resolve(
+                        env,
+                        native_class_finder,
+                        field_type,
+                        field_ref.class_name.as_str(),
+                        field_ref.field_name.as_str(),
+                        field_ref.field_signature.as_str(),
+                        field_ref.java_type.clone(),
+                    )
+                    .unwrap_or_else(|e| {
+                        panic!(
+                            "cannot cache the field [{}.{}: {}] : [{}]",
+                            field_ref.class_name,
+                            field_ref.field_name,
+                            field_ref.field_signature,
+                            e
                         )
-                        .unwrap();
-                    let new_method = JavaStaticMethod::new(
-                        instance_ref_2,
-                        method_ref.class_name.as_str(),
-                        method_ref.method_name().as_str(),
-                        method_ref.method_signature().as_str(),
-                        method_id,
-                    );
+                    });
+                    self.push(new_field);
+                }
+            }
+        }
+
+        #[derive(Debug)]
+        pub struct JavaFieldsListRefs {
+            fields_list: Vec<FieldItemRef>,
+        }
+
+        impl JavaFieldsListRefs {
+            pub fn new() -> Self {
+                Self {
+                    fields_list: vec![],
+                }
+            }
+
+            pub fn add(
+                &mut self,
+                field_type: FieldType,
+                class_name: &str,
+                field_name: &str,
+                signature: &str,
+            ) -> FieldHandle {
+                let index = self
+                    .fields_list
+                    .iter()
+                    .filter(|item| item.field_type == field_type)
+                    .count();
+                let java_type = parse_field_signature(class_name, field_name, signature)
+                    .unwrap_or_else(|e| panic!("{}", e));
+
+                self.fields_list.push(FieldItemRef {
+                    field_type,
+                    class_name: class_name.to_owned(),
+                    field_name: field_name.to_owned(),
+                    field_signature: signature.to_owned(),
+                    java_type,
+                });
+                FieldHandle::new(field_type, index)
+            }
+        }
+
+        #[derive(Debug)]
+        struct FieldItemRef {
+            field_type: FieldType,
+            class_name: String,
+            field_name: String,
+            field_signature: String,
+            java_type: JavaType,
+        }
+    }
+
+    pub mod java_method_cache_utils {
+        use std::fmt::Debug;
+
+        use jni::objects::{
+            JBooleanArray, JByteArray, JCharArray, JFloatArray, JIntArray, JLongArray,
+            JObjectArray, JShortArray, JValueGen,
+        };
+
+        use super::*;
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum MethodType {
+            Static,
+            NonStatic,
+            /// A constructor, registered with the name "<init>" and a void signature.
+            Constructor,
+        }
+
+        /// Token returned by JavaMethodCacheBuilder::add_java_method.
+        /// Passing it to the call APIs skips the lookup by class, name and signature.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct MethodHandle {
+            method_type: MethodType,
+            index: usize,
+        }
+
+        impl MethodHandle {
+            pub(crate) fn new(method_type: MethodType, index: usize) -> Self {
+                Self { method_type, index }
+            }
+            pub fn method_type(&self) -> MethodType {
+                self.method_type
+            }
+            pub fn index(&self) -> usize {
+                self.index
+            }
+        }
+
+        /// Designates a cached method, either by handle or by its class, name and signature.
+        #[derive(Debug, Clone)]
+        pub enum JavaMethodRef {
+            Handle(MethodHandle),
+            Name {
+                class: String,
+                name: String,
+                sig: String,
+            },
+        }
+
+        impl JavaMethodRef {
+            pub fn by_name(class: &str, name: &str, sig: &str) -> Self {
+                JavaMethodRef::Name {
+                    class: class.to_owned(),
+                    name: name.to_owned(),
+                    sig: sig.to_owned(),
+                }
+            }
+            pub fn method_name(&self) -> String {
+                match self {
+                    JavaMethodRef::Handle(handle) => format!("{:?}", handle),
+                    JavaMethodRef::Name { name, .. } => name.to_string(),
+                }
+            }
+        }
+
+        impl From<MethodHandle> for JavaMethodRef {
+            fn from(handle: MethodHandle) -> Self {
+                JavaMethodRef::Handle(handle)
+            }
+        }
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum FieldType {
+            Static,
+            NonStatic,
+        }
+
+        /// Token returned by JavaMethodCacheBuilder::add_java_field.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct FieldHandle {
+            field_type: FieldType,
+            index: usize,
+        }
 
-                    self.methods_list.push(new_method);
-                }
+        impl FieldHandle {
+            pub(crate) fn new(field_type: FieldType, index: usize) -> Self {
+                Self { field_type, index }
+            }
+            pub fn field_type(&self) -> FieldType {
+                self.field_type
+            }
+            pub fn index(&self) -> usize {
+                self.index
             }
         }
 
-        #[derive(Debug)]
-        pub struct JavaMethodsListRefs<'a> {
-            methods_list: Vec<MethodItemRef<'a>>,
+        /// Designates a cached field, either by handle or by its class, name and signature.
+        #[derive(Debug, Clone)]
+        pub enum JavaFieldRef {
+            Handle(FieldHandle),
+            Name {
+                class: String,
+                name: String,
+                sig: String,
+            },
         }
 
-        impl<'a> JavaMethodsListRefs<'a> {
-            pub fn new() -> Self {
-                Self {
-                    methods_list: vec![],
+        impl JavaFieldRef {
+            pub fn by_name(class: &str, name: &str, sig: &str) -> Self {
+                JavaFieldRef::Name {
+                    class: class.to_owned(),
+                    name: name.to_owned(),
+                    sig: sig.to_owned(),
                 }
             }
+            pub fn field_name(&self) -> String {
+                match self {
+                    JavaFieldRef::Handle(handle) => format!("{:?}", handle),
+                    JavaFieldRef::Name { name, .. } => name.to_string(),
+                }
+            }
+        }
 
-            pub fn add(
-                &mut self,
-                env: &JNIEnv<'a>,
-                method_type: MethodType,
-                class_name: &str,
-                method_name: &str,
-                signature: &str,
-            ) {
-                let class: jni::objects::JObject = env.new_string(class_name).unwrap().into();
-                let method_name: String = method_name.to_string();
-                let signature: String = signature.to_string();
-
-                let new_method = MethodItemRef::new(
-                    method_type,
-                    class,
-                    class_name.to_owned(),
-                    method_name,
-                    signature,
-                );
-
-                self.methods_list.push(new_method);
+        impl From<FieldHandle> for JavaFieldRef {
+            fn from(handle: FieldHandle) -> Self {
+                JavaFieldRef::Handle(handle)
             }
         }
 
-        #[derive(Debug)]
-        pub struct MethodItemRef<'a> {
-            class: jni::objects::JObject<'a>,
-            method_type: MethodType,
-            class_name: String,
-            method_name: String,
-            method_signature: String,
+        /// Id of an object stored in the JObjectStore : a name chosen by the caller, or a
+        /// number minted by the store for the objects returned without a name.
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum ObjectId {
+            Named(String),
+            Generated(u64),
         }
 
-        impl<'a> MethodItemRef<'a> {
-            pub fn new(
-                method_type: MethodType,
-                class: jni::objects::JObject<'a>,
-                class_name: String,
-                method_name: String,
-                method_signature: String,
-            ) -> Self {
-                Self {
-                    method_type,
-                    class,
-                    class_name,
-                    method_name,
-                    method_signature,
-                }
-            }
-            pub fn method_name(&self) -> String {
-                self.method_name.to_string()
+        impl ObjectId {
+            pub fn new(id: &str) -> Self {
+                ObjectId::Named(id.to_string())
             }
-            pub fn method_signature(&self) -> String {
-                self.method_signature.to_string()
+            pub fn is_generated(&self) -> bool {
+                matches!(self, ObjectId::Generated(_))
             }
         }
-    }
 
-    pub mod java_method_cache_utils {
-        use std::fmt::Debug;
+        impl std::fmt::Display for ObjectId {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    ObjectId::Named(name) => write!(f, "{}", name),
+                    ObjectId::Generated(n) => write!(f, "#{}", n),
+                }
+            }
+        }
 
-        use jni::objects::{JIntArray, JValueGen};
+        impl From<&str> for ObjectId {
+            fn from(id: &str) -> Self {
+                ObjectId::new(id)
+            }
+        }
 
-        use super::*;
+        impl From<String> for ObjectId {
+            fn from(id: String) -> Self {
+                ObjectId::Named(id)
+            }
+        }
 
-        #[derive(Debug)]
-        pub enum MethodType {
-            Static,
-            NonStatic,
+        impl From<&ObjectId> for ObjectId {
+            fn from(id: &ObjectId) -> Self {
+                id.clone()
+            }
         }
 
         #[derive(Debug)]
         pub enum JavaArgs {
-            JObject(String),
+            JObject(ObjectId),
+            Bool(bool),
+            Byte(i8),
+            Char(u16),
+            Short(i16),
             I32(i32),
-            F32,
+            Long(i64),
+            Float(f32),
+            Double(f64),
+            String(String),
             None,
             Array(Vec<JavaArgs>),
         }
 
+        /// jvalues ready to be passed to a java call, with the local references created
+        /// for them (java strings) that can be deleted once the call is done, and the
+        /// stored objects they point to, kept alive until the call returns.
+        pub struct JValueArgs<'local> {
+            values: Vec<jni::sys::jvalue>,
+            locals: Vec<JObject<'local>>,
+            globals: Vec<GlobalRef>,
+        }
+
+        impl<'local> JValueArgs<'local> {
+            pub fn values(&self) -> &[jni::sys::jvalue] {
+                &self.values[..]
+            }
+            pub fn delete_locals(self, env: &mut jni::JNIEnv) {
+                for local in self.locals {
+                    let _ = env.delete_local_ref(local);
+                }
+            }
+        }
+
         impl JavaArgs {
-            pub fn to_jvalue(
+            pub fn len(&self) -> usize {
+                match self {
+                    JavaArgs::None => 0,
+                    JavaArgs::Array(arr) => arr.len(),
+                    _ => 1,
+                }
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+
+            /// Converts the arguments to jvalues, after checking them against the parsed
+            /// argument list of the method signature. The errors report method_signature,
+            /// the signature string the method was registered with.
+            /// A stored object is checked to be an instance of its parameter type ; the
+            /// store is only locked to clone its reference, the check can run java code.
+            pub fn to_jvalue<'local>(
+                &self,
+                env: &mut jni::JNIEnv<'local>,
+                instanciated_j_objects: &Mutex<JObjectStore>,
+                native_class_finder: &NativeClassFinder,
+                signature: &TypeSignature,
+                method_signature: &str,
+            ) -> std::result::Result<JValueArgs<'local>, JniCacheError> {
+                if self.len() != signature.args.len() {
+                    return Err(JniCacheError::ArgumentMismatch {
+                        sig: method_signature.to_string(),
+                        message: format!(
+                            "expected {} argument(s), got {}",
+                            signature.args.len(),
+                            self.len()
+                        ),
+                    });
+                }
+                let mut args = JValueArgs {
+                    values: vec![],
+                    locals: vec![],
+                    globals: vec![],
+                };
+                let items: Vec<&JavaArgs> = match self {
+                    JavaArgs::None => vec![],
+                    JavaArgs::Array(arr) => arr.iter().collect(),
+                    single => vec![single],
+                };
+                for (item, java_type) in items.into_iter().zip(signature.args.iter()) {
+                    item.check_type(java_type, method_signature)?;
+                    let value = item.to_single_jvalue(
+                        env,
+                        instanciated_j_objects,
+                        native_class_finder,
+                        java_type,
+                        method_signature,
+                        &mut args,
+                    )?;
+                    args.values.push(value);
+                }
+
+                Ok(args)
+            }
+
+            fn check_type(
+                &self,
+                java_type: &JavaType,
+                signature: &str,
+            ) -> std::result::Result<(), JniCacheError> {
+                let matches = match (self, java_type) {
+                    (JavaArgs::JObject(_), JavaType::Object(_) | JavaType::Array(_)) => true,
+                    (JavaArgs::String(_), JavaType::Object(class)) => matches!(
+                        class.as_str(),
+                        "java/lang/String" | "java/lang/CharSequence" | "java/lang/Object"
+                    ),
+                    (JavaArgs::Bool(_), JavaType::Primitive(Primitive::Boolean)) => true,
+                    (JavaArgs::Byte(_), JavaType::Primitive(Primitive::Byte)) => true,
+                    (JavaArgs::Char(_), JavaType::Primitive(Primitive::Char)) => true,
+                    (JavaArgs::Short(_), JavaType::Primitive(Primitive::Short)) => true,
+                    (JavaArgs::I32(_), JavaType::Primitive(Primitive::Int)) => true,
+                    (JavaArgs::Long(_), JavaType::Primitive(Primitive::Long)) => true,
+                    (JavaArgs::Float(_), JavaType::Primitive(Primitive::Float)) => true,
+                    (JavaArgs::Double(_), JavaType::Primitive(Primitive::Double)) => true,
+                    _ => false,
+                };
+                if matches {
+                    Ok(())
+                } else {
+                    Err(JniCacheError::ArgumentMismatch {
+                        sig: signature.to_string(),
+                        message: format!("[{:?}] given where [{}] is expected", self, java_type),
+                    })
+                }
+            }
+
+            /// Fails with ArgumentMismatch when the stored object is not an instance of
+            /// java_type.
+            fn check_instance(
+                &self,
+                env: &mut jni::JNIEnv,
+                native_class_finder: &NativeClassFinder,
+                object: &JObject,
+                java_type: &JavaType,
+                signature: &str,
+            ) -> std::result::Result<(), JniCacheError> {
+                if native_class_finder.is_instance_of(env, object, java_type)? {
+                    return Ok(());
+                }
+                Err(JniCacheError::ArgumentMismatch {
+                    sig: signature.to_string(),
+                    message: format!("[{:?}] is not an instance of [{}]", self, java_type),
+                })
+            }
+
+            /// Converts a single argument to the value written to a field of type java_type.
+            /// Objects become local references, freed with the local frame of the call.
+            pub fn to_field_value<'local>(
                 &self,
-                instanciated_j_objects: &JObjectStore,
-            ) -> Option<Vec<jni::sys::jvalue>> {
+                env: &mut jni::JNIEnv<'local>,
+                instanciated_j_objects: &Mutex<JObjectStore>,
+                native_class_finder: &NativeClassFinder,
+                java_type: &JavaType,
+                signature: &str,
+            ) -> std::result::Result<JValueOwned<'local>, JniCacheError> {
+                self.check_type(java_type, signature)?;
                 match self {
                     JavaArgs::JObject(o_id) => {
-                        if let Some(found_object) = instanciated_j_objects.find(o_id) {
-                            Some(vec![JValueGen::Object(found_object).as_jni()])
-                        } else {
-                            None
-                        }
+                        let found_object = instanciated_j_objects
+                            .lock()
+                            .find(o_id)
+                            .cloned()
+                            .ok_or_else(|| JniCacheError::ObjectNotFound(o_id.clone()))?;
+                        self.check_instance(
+                            env,
+                            native_class_finder,
+                            found_object.as_obj(),
+                            java_type,
+                            signature,
+                        )?;
+                        Ok(JValueGen::Object(env.new_local_ref(&found_object)?))
                     }
-                    JavaArgs::Array(arr) => {
-                        let mut args_arr: Vec<jni::sys::jvalue> = vec![];
-
-                        for item in arr.iter() {
-                            match item {
-                                JavaArgs::JObject(o_id) => {
-                                    if let Some(found_object) = instanciated_j_objects.find(o_id) {
-                                        args_arr.push(JValueGen::Object(found_object).as_jni());
-                                    }
-                                }
-                                JavaArgs::I32(v) => {
-                                    args_arr.push(JValue::from(*v).as_jni());
-                                }
-                                _ => {}
-                            };
-                        }
+                    JavaArgs::String(s) => {
+                        let j_string = env.new_string(s).map_err(|e| {
+                            JniCacheError::ArgumentConversion(format!(
+                                "cannot create a java string from [{}] : [{:?}]",
+                                s, e
+                            ))
+                        })?;
+                        Ok(JValueGen::Object(j_string.into()))
+                    }
+                    JavaArgs::Bool(v) => Ok(JValueGen::Bool(*v as jni::sys::jboolean)),
+                    JavaArgs::Byte(v) => Ok(JValueGen::Byte(*v)),
+                    JavaArgs::Char(v) => Ok(JValueGen::Char(*v)),
+                    JavaArgs::Short(v) => Ok(JValueGen::Short(*v)),
+                    JavaArgs::I32(v) => Ok(JValueGen::Int(*v)),
+                    JavaArgs::Long(v) => Ok(JValueGen::Long(*v)),
+                    JavaArgs::Float(v) => Ok(JValueGen::Float(*v)),
+                    JavaArgs::Double(v) => Ok(JValueGen::Double(*v)),
+                    other => Err(JniCacheError::ArgumentConversion(format!(
+                        "[{:?}] cannot be written to a java field",
+                        other
+                    ))),
+                }
+            }
 
-                        if args_arr.len() > 0 {
-                            Some(args_arr)
-                        } else {
-                            None
-                        }
+            fn to_single_jvalue<'local>(
+                &self,
+                env: &mut jni::JNIEnv<'local>,
+                instanciated_j_objects: &Mutex<JObjectStore>,
+                native_class_finder: &NativeClassFinder,
+                java_type: &JavaType,
+                signature: &str,
+                args: &mut JValueArgs<'local>,
+            ) -> std::result::Result<jni::sys::jvalue, JniCacheError> {
+                match self {
+                    JavaArgs::JObject(o_id) => {
+                        let found_object = instanciated_j_objects
+                            .lock()
+                            .find(o_id)
+                            .cloned()
+                            .ok_or_else(|| JniCacheError::ObjectNotFound(o_id.clone()))?;
+                        self.check_instance(
+                            env,
+                            native_class_finder,
+                            found_object.as_obj(),
+                            java_type,
+                            signature,
+                        )?;
+                        let value = JValueGen::Object(found_object.as_obj()).as_jni();
+                        args.globals.push(found_object);
+                        Ok(value)
                     }
-                    JavaArgs::None => None,
-                    _ => None,
+                    JavaArgs::String(s) => {
+                        let j_string: JObject = env
+                            .new_string(s)
+                            .map_err(|e| {
+                                JniCacheError::ArgumentConversion(format!(
+                                    "cannot create a java string from [{}] : [{:?}]",
+                                    s, e
+                                ))
+                            })?
+                            .into();
+                        let value = JValueGen::Object(&j_string).as_jni();
+                        args.locals.push(j_string);
+                        Ok(value)
+                    }
+                    JavaArgs::Bool(v) => Ok(JValue::from(*v).as_jni()),
+                    JavaArgs::Byte(v) => Ok(JValue::from(*v).as_jni()),
+                    JavaArgs::Char(v) => Ok(JValue::Char(*v).as_jni()),
+                    JavaArgs::Short(v) => Ok(JValue::from(*v).as_jni()),
+                    JavaArgs::I32(v) => Ok(JValue::from(*v).as_jni()),
+                    JavaArgs::Long(v) => Ok(JValue::from(*v).as_jni()),
+                    JavaArgs::Float(v) => Ok(JValue::from(*v).as_jni()),
+                    JavaArgs::Double(v) => Ok(JValue::from(*v).as_jni()),
+                    other => Err(JniCacheError::ArgumentConversion(format!(
+                        "[{:?}] cannot be passed as a single java argument",
+                        other
+                    ))),
                 }
             }
         }
 
-        #[derive(Clone)]
+        #[derive(Debug, Clone)]
         pub enum ReturnedValue {
+            Bool(bool),
+            Byte(i8),
+            Char(u16),
+            Short(i16),
             I32(i32),
-            JObject(String),
-            Void,
             Long(i64),
-            ArrayFloat,
+            Float(f32),
+            Double(f64),
+            JObject(ObjectId),
+            Null,
+            Void,
             String(String),
+            VecFloat(Vec<f32>),
             VecDouble(Vec<f64>),
-            VecUsize(Vec<usize>),
+            VecI32(Vec<i32>),
+            VecLong(Vec<i64>),
+            VecU8(Vec<u8>),
+            VecBool(Vec<bool>),
+            VecShort(Vec<i16>),
+            VecChar(Vec<u16>),
+            VecString(Vec<Option<String>>),
         }
-        enum Extractible<'a> {
-            Yes(&'a str, JObject<'a>, &'a mut jni::JNIEnv<'a>),
-            No,
+        impl ReturnedValue {
+            pub fn variant_name(&self) -> &'static str {
+                match self {
+                    ReturnedValue::Bool(_) => "Bool",
+                    ReturnedValue::Byte(_) => "Byte",
+                    ReturnedValue::Char(_) => "Char",
+                    ReturnedValue::Short(_) => "Short",
+                    ReturnedValue::I32(_) => "I32",
+                    ReturnedValue::Long(_) => "Long",
+                    ReturnedValue::Float(_) => "Float",
+                    ReturnedValue::Double(_) => "Double",
+                    ReturnedValue::JObject(_) => "JObject",
+                    ReturnedValue::Null => "Null",
+                    ReturnedValue::Void => "Void",
+                    ReturnedValue::String(_) => "String",
+                    ReturnedValue::VecFloat(_) => "VecFloat",
+                    ReturnedValue::VecDouble(_) => "VecDouble",
+                    ReturnedValue::VecI32(_) => "VecI32",
+                    ReturnedValue::VecLong(_) => "VecLong",
+                    ReturnedValue::VecU8(_) => "VecU8",
+                    ReturnedValue::VecBool(_) => "VecBool",
+                    ReturnedValue::VecShort(_) => "VecShort",
+                    ReturnedValue::VecChar(_) => "VecChar",
+                    ReturnedValue::VecString(_) => "VecString",
+                }
+            }
         }
+
         impl ReturnedValue {
-            pub fn get_result_type<'a>(
-                env: &'a mut jni::JNIEnv<'a>,
-                result: JValueOwned<'a>,
-                standard_class_list: &'a StandardClassCache<'a>,
-                object_store_id: Option<String>,
-                j_object_method_store: &mut JObjectStore<'a>,
-            ) -> ReturnedValue {
-                match result {
+            /// Converts the result of a call ; an object that is not extracted to a rust
+            /// value is stored, the store is only locked to insert it.
+            pub fn get_result_type<'local>(
+                env: &mut jni::JNIEnv<'local>,
+                result: JValueOwned<'local>,
+                standard_class_list: &StandardClassCache,
+                object_store_id: Option<ObjectId>,
+                j_object_method_store: &Mutex<JObjectStore>,
+            ) -> std::result::Result<ReturnedValue, JniCacheError> {
+                Ok(match result {
+                    JValueGen::Object(o) if o.is_null() => ReturnedValue::Null,
                     JValueGen::Object(o) => {
-                        match check_if_extractible_classes(env, standard_class_list, o.as_raw()) {
-                            Extractible::Yes(class_name, obj, env_passed) => {
-                                extract_value(env_passed, class_name, obj).unwrap()
-                            }
-                            Extractible::No => {
-                                j_object_method_store.add_object_with_id(
-                                    &object_store_id.clone().expect("no object identifier given"),
-                                    o,
-                                );
-                                ReturnedValue::JObject(
-                                    object_store_id.expect("no object identifer given"),
-                                )
+                        match check_if_extractible_classes(env, standard_class_list, &o)? {
+                            Some(class_name) => extract_value(env, class_name, o)?,
+                            None => {
+                                let object_ref = env.new_global_ref(&o)?;
+                                env.delete_local_ref(o)?;
+                                let mut objects = j_object_method_store.lock();
+                                let id = match object_store_id {
+                                    Some(id) => {
+                                        objects.add_object_with_id(&id, object_ref);
+                                        id
+                                    }
+                                    None => objects.add_object(object_ref),
+                                };
+                                ReturnedValue::JObject(id)
                             }
                         }
                     }
+                    JValueGen::Bool(b) => ReturnedValue::Bool(b != 0),
+                    JValueGen::Byte(byte) => ReturnedValue::Byte(byte),
+                    JValueGen::Char(c) => ReturnedValue::Char(c),
+                    JValueGen::Short(short) => ReturnedValue::Short(short),
+                    JValueGen::Int(int) => ReturnedValue::I32(int),
                     JValueGen::Long(long) => ReturnedValue::Long(long),
-                    _ => ReturnedValue::Void,
-                }
+                    JValueGen::Float(float) => ReturnedValue::Float(float),
+                    JValueGen::Double(double) => ReturnedValue::Double(double),
+                    JValueGen::Void => ReturnedValue::Void,
+                })
             }
         }
 
+        /// Classes whose instances extract_value turns into rust values.
+        const EXTRACTIBLE_CLASSES: [&str; 10] = [
+            "java/lang/String",
+            "[D",
+            "[I",
+            "[F",
+            "[J",
+            "[B",
+            "[Z",
+            "[S",
+            "[C",
+            "[Ljava/lang/String;",
+        ];
+
+        pub fn is_extractible(class_name: &str) -> bool {
+            EXTRACTIBLE_CLASSES.contains(&class_name)
+        }
+
         fn extract_value<'a>(
             env: &mut jni::JNIEnv<'a>,
             class_name: &str,
             object: JObject<'a>,
-        ) -> Option<ReturnedValue> {
+        ) -> std::result::Result<ReturnedValue, JniCacheError> {
             match class_name {
                 "java/lang/String" => {
                     let j_string: JString = JString::from(object);
                     let rust_string: String =
-                        unsafe { env.get_string_unchecked(&j_string).unwrap().into() };
-                    Some(ReturnedValue::String(rust_string))
+                        unsafe { env.get_string_unchecked(&j_string)?.into() };
+                    Ok(ReturnedValue::String(rust_string))
                 }
                 "[D" => {
                     let j_double_array: JDoubleArray = JDoubleArray::from(object);
 
-                    let length: usize = env.get_array_length(&j_double_array).unwrap() as usize;
+                    let length: usize = env.get_array_length(&j_double_array)? as usize;
                     let mut buffer: Vec<jni::sys::jdouble> = vec![0.0; length];
-
-                    let _double_array_region = env
-                        .get_double_array_region(j_double_array, 0, &mut buffer)
-                        .unwrap();
-
-                    Some(ReturnedValue::VecDouble(
-                        buffer.into_iter().map(|i| i as f64).collect(),
-                    ))
+                    env.get_double_array_region(j_double_array, 0, &mut buffer)?;
+                    Ok(ReturnedValue::VecDouble(buffer))
                 }
                 "[I" => {
                     let j_int_array: JIntArray = JIntArray::from(object);
 
-                    let length: usize = env.get_array_length(&j_int_array).unwrap() as usize;
+                    let length: usize = env.get_array_length(&j_int_array)? as usize;
                     let mut buffer: Vec<jni::sys::jint> = vec![0; length];
-                    let _int_array_region = env
-                        .get_int_array_region(j_int_array, 0, &mut buffer)
-                        .unwrap();
-                    Some(ReturnedValue::VecUsize(
-                        buffer.into_iter().map(|i| i as usize).collect(),
+                    env.get_int_array_region(j_int_array, 0, &mut buffer)?;
+                    Ok(ReturnedValue::VecI32(buffer))
+                }
+                "[F" => {
+                    let j_float_array: JFloatArray = JFloatArray::from(object);
+
+                    let length: usize = env.get_array_length(&j_float_array)? as usize;
+                    let mut buffer: Vec<jni::sys::jfloat> = vec![0.0; length];
+                    env.get_float_array_region(j_float_array, 0, &mut buffer)?;
+                    Ok(ReturnedValue::VecFloat(buffer))
+                }
+                "[J" => {
+                    let j_long_array: JLongArray = JLongArray::from(object);
+
+                    let length: usize = env.get_array_length(&j_long_array)? as usize;
+                    let mut buffer: Vec<jni::sys::jlong> = vec![0; length];
+                    env.get_long_array_region(j_long_array, 0, &mut buffer)?;
+                    Ok(ReturnedValue::VecLong(buffer))
+                }
+                "[B" => {
+                    let j_byte_array: JByteArray = JByteArray::from(object);
+
+                    let bytes: Vec<u8> = env.convert_byte_array(j_byte_array)?;
+                    Ok(ReturnedValue::VecU8(bytes))
+                }
+                "[Z" => {
+                    let j_boolean_array: JBooleanArray = JBooleanArray::from(object);
+
+                    let length: usize = env.get_array_length(&j_boolean_array)? as usize;
+                    let mut buffer: Vec<jni::sys::jboolean> = vec![0; length];
+                    env.get_boolean_array_region(j_boolean_array, 0, &mut buffer)?;
+                    Ok(ReturnedValue::VecBool(
+                        buffer.into_iter().map(|b| b != 0).collect(),
                     ))
                 }
-                _ => None,
+                "[S" => {
+                    let j_short_array: JShortArray = JShortArray::from(object);
+
+                    let length: usize = env.get_array_length(&j_short_array)? as usize;
+                    let mut buffer: Vec<jni::sys::jshort> = vec![0; length];
+                    env.get_short_array_region(j_short_array, 0, &mut buffer)?;
+                    Ok(ReturnedValue::VecShort(buffer))
+                }
+                "[C" => {
+                    let j_char_array: JCharArray = JCharArray::from(object);
+
+                    let length: usize = env.get_array_length(&j_char_array)? as usize;
+                    let mut buffer: Vec<jni::sys::jchar> = vec![0; length];
+                    env.get_char_array_region(j_char_array, 0, &mut buffer)?;
+                    Ok(ReturnedValue::VecChar(buffer))
+                }
+                "[Ljava/lang/String;" => {
+                    let j_object_array: JObjectArray = JObjectArray::from(object);
+
+                    let length = env.get_array_length(&j_object_array)?;
+                    let mut strings: Vec<Option<String>> = Vec::with_capacity(length as usize);
+                    for i in 0..length {
+                        let j_string: JString =
+                            env.get_object_array_element(&j_object_array, i)?.into();
+                        if j_string.is_null() {
+                            strings.push(None);
+                        } else {
+                            strings.push(Some(env.get_string(&j_string)?.into()));
+                        }
+                        env.delete_local_ref(j_string)?;
+                    }
+                    Ok(ReturnedValue::VecString(strings))
+                }
+                _ => Err(JniCacheError::ResolutionFailed {
+                    member: class_name.to_string(),
+                    reason: "its instances cannot be returned as rust values".to_string(),
+                }),
             }
         }
 
-        fn check_if_extractible_classes<'a>(
-            env: &'a mut jni::JNIEnv<'a>,
-            standard_class_list: &'a StandardClassCache<'a>,
-            o: *mut jni::sys::_jobject,
-        ) -> Extractible<'a> {
-            let mut extractible: Extractible = Extractible::No;
-
-            let object_class: JClass = env
-                .get_object_class(unsafe { JObject::from_raw(o) })
-                .unwrap();
+        fn check_if_extractible_classes<'c>(
+            env: &mut jni::JNIEnv,
+            standard_class_list: &'c StandardClassCache,
+            o: &JObject,
+        ) -> std::result::Result<Option<&'c str>, JniCacheError> {
             for class in standard_class_list.inner.iter() {
                 let (class_name_ref, class) = class.get_class_ref();
 
-                let is_std_class: bool = env
-                    .is_instance_of(unsafe { JObject::from_raw(o) }, class)
-                    .unwrap();
-
-                if is_std_class {
-                    extractible =
-                        Extractible::Yes(class_name_ref, unsafe { JObject::from_raw(o) }, env);
-                    break;
+                if env.is_instance_of(o, class)? {
+                    return Ok(Some(class_name_ref));
                 }
             }
-            extractible
+            Ok(None)
         }
     }
 
@@ -949,43 +2503,86 @@ pub mod java_method_build_tools {
 
         use super::*;
 
-        pub struct JObjectStore<'a> {
-            inner: Vec<JObjectEntry<'a>>,
+        pub struct JObjectStore {
+            inner: Vec<JObjectEntry>,
+            next_generated_id: u64,
         }
 
-        impl<'a> JObjectStore<'a> {
+        impl JObjectStore {
             pub fn new() -> Self {
-                Self { inner: vec![] }
+                Self {
+                    inner: vec![],
+                    next_generated_id: 0,
+                }
             }
 
-            pub fn find(&self, j_object_id: &str) -> Option<&JObject<'a>> {
-                if let Some(entry) = self
-                    .inner
-                    .iter()
-                    .find(|item| item.id.as_str() == j_object_id)
-                {
+            pub fn find(&self, j_object_id: &ObjectId) -> Option<&GlobalRef> {
+                if let Some(entry) = self.inner.iter().find(|item| &item.id == j_object_id) {
                     Some(&entry.object)
                 } else {
                     None
                 }
             }
 
-            pub fn add_object_with_id(&mut self, id: &str, j_object: JObject<'a>) -> &mut Self {
-                let new_object = JObjectEntry::new(id, j_object);
-                self.inner.push(new_object);
+            /// Stores j_object under a newly minted id.
+            pub fn add_object(&mut self, j_object: GlobalRef) -> ObjectId {
+                let id = ObjectId::Generated(self.next_generated_id);
+                self.next_generated_id += 1;
+                self.inner.push(JObjectEntry::new(&id, j_object));
+                id
+            }
+
+            /// Stores j_object under id, an object already stored with this id is released.
+            pub fn add_object_with_id(&mut self, id: &ObjectId, j_object: GlobalRef) -> &mut Self {
+                self.replace(id, j_object);
                 self
             }
+
+            /// Stores j_object under id and gives back the object it replaces, if any.
+            pub fn replace(&mut self, id: &ObjectId, j_object: GlobalRef) -> Option<GlobalRef> {
+                match self.inner.iter_mut().find(|item| &item.id == id) {
+                    Some(entry) => Some(mem::replace(&mut entry.object, j_object)),
+                    None => {
+                        self.inner.push(JObjectEntry::new(id, j_object));
+                        None
+                    }
+                }
+            }
+
+            /// Removes the object from the store. The java reference is deleted when the
+            /// returned GlobalRef is dropped.
+            pub fn remove(&mut self, id: &ObjectId) -> Option<GlobalRef> {
+                let position = self.inner.iter().position(|item| &item.id == id)?;
+                Some(self.inner.remove(position).object)
+            }
+
+            pub fn ids(&self) -> Vec<ObjectId> {
+                self.inner.iter().map(|item| item.id.clone()).collect()
+            }
+
+            pub fn len(&self) -> usize {
+                self.inner.len()
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.inner.is_empty()
+            }
+
+            /// Releases every object, the minted ids are never given again.
+            pub fn clear(&mut self) {
+                self.inner.clear();
+            }
         }
 
-        struct JObjectEntry<'a> {
-            id: String,
-            object: JObject<'a>,
+        struct JObjectEntry {
+            id: ObjectId,
+            object: GlobalRef,
         }
 
-        impl<'a> JObjectEntry<'a> {
-            fn new(id: &str, object: JObject<'a>) -> JObjectEntry<'a> {
+        impl JObjectEntry {
+            fn new(id: &ObjectId, object: GlobalRef) -> JObjectEntry {
                 Self {
-                    id: id.to_string(),
+                    id: id.clone(),
                     object,
                 }
             }
@@ -1009,184 +2606,456 @@ pub mod java_method_build_tools {
             }
         }
 
-        pub struct StandardClassCache<'a> {
-            pub inner: Vec<StandardClass<'a>>,
-            index: usize,
+        pub struct StandardClassCache {
+            pub inner: Vec<StandardClass>,
         }
 
-        impl<'a> StandardClassCache<'a> {
+        impl StandardClassCache {
             pub fn new() -> Self {
-                Self {
-                    inner: vec![],
-                    index: 0,
-                }
+                Self { inner: vec![] }
             }
 
             pub fn build_standard_class_list(
                 &mut self,
-                env: &mut jni::JNIEnv<'a>,
+                env: &mut jni::JNIEnv,
                 standard_class_pre_list: &StandardClassPreList,
             ) {
                 for class_name in standard_class_pre_list.list.iter() {
-                    match env.find_class(class_name) {
-                        Ok(class) => {
-                            let new_std_class = StandardClass::new(class_name, class);
-                            self.inner.push(new_std_class);
-                        }
+                    if !is_extractible(class_name) {
+                        log::warn!("class [{}] cannot be returned as a rust value", class_name);
+                        continue;
+                    }
+                    match StandardClass::find(env, class_name) {
+                        Ok(new_std_class) => self.inner.push(new_std_class),
                         _ => {
-                            println!("---Class [{:?}] not found in JNIEnv", class_name)
+                            log::warn!("class [{}] not found in JNIEnv", class_name)
                         }
                     }
                 }
             }
+
+            pub fn contains(&self, class_name: &str) -> bool {
+                self.inner
+                    .iter()
+                    .any(|std_class| std_class.class_name() == class_name)
+            }
+
+            /// Adds the class unless a class of the same name is already cached.
+            pub fn insert(&mut self, std_class: StandardClass) {
+                if !self.contains(std_class.class_name()) {
+                    self.inner.push(std_class);
+                }
+            }
         }
 
-        pub struct StandardClass<'a> {
+        pub struct StandardClass {
             class_name: String,
-            class: JClass<'a>,
+            class: GlobalRef,
         }
-        impl<'a> StandardClass<'a> {
-            pub fn new(class_name: &str, class: JClass<'a>) -> Self {
+        impl StandardClass {
+            pub fn new(class_name: &str, class: GlobalRef) -> Self {
                 Self {
                     class_name: class_name.to_string(),
                     class,
                 }
             }
-            pub fn class(&self) -> &JClass {
-                &self.class
+
+            /// Finds the class with the JNIEnv, a NoClassDefFoundError is cleared and returned.
+            pub fn find(
+                env: &mut jni::JNIEnv,
+                class_name: &str,
+            ) -> std::result::Result<StandardClass, JniCacheError> {
+                let class = env.find_class(class_name);
+                check_exception(env)?;
+                let class = class?;
+                let class_ref = env.new_global_ref(&class)?;
+                env.delete_local_ref(class)?;
+                Ok(StandardClass::new(class_name, class_ref))
+            }
+            pub fn class(&self) -> &JClass<'static> {
+                <&JClass>::from(self.class.as_obj())
             }
             pub fn class_name(&self) -> &str {
                 self.class_name.as_str()
             }
 
-            pub fn get_class_ref(&self) -> (&str, &JClass) {
-                (self.class_name.as_str(), &self.class)
+            pub fn get_class_ref(&self) -> (&str, &JClass<'static>) {
+                (self.class_name.as_str(), self.class())
             }
         }
     }
 }
 
 mod java_vm_response {
-    use std::{fmt::Debug, ops::Deref, time::Instant};
+    use std::future::{poll_fn, Future};
+    use std::pin::pin;
+    use std::task::{Poll, Waker};
+    use std::time::Instant;
 
-    use crate::ReturnedValue;
+    use crate::jni_methods_cache::error::JniCacheError;
+    use crate::{ObjectId, ReturnedValue};
 
+    type JVMResultMessage = std::result::Result<ReturnedValue, JniCacheError>;
+
+    /// Channel of a single call, the caller blocks until it is answered. The sender is
+    /// the only one : if the executor drops it without answering, the wait fails.
     #[derive(Debug)]
     pub struct JVMResult {
-        channel: (
-            kanal::Sender<(ReturnedValue, Instant)>,
-            kanal::Receiver<(ReturnedValue, Instant)>,
-        ),
+        receiver: kanal::Receiver<JVMResultMessage>,
     }
     impl JVMResult {
-        pub fn new() -> Self {
-            Self {
-                channel: kanal::bounded(1),
+        pub fn new() -> (JVMResultSender, Self) {
+            let (sender, receiver) = kanal::bounded(1);
+            (JVMResultSender { sender }, Self { receiver })
+        }
+
+        /// Gives up with Timeout once the deadline has passed, the receiver is dropped so
+        /// a late answer of the executor is discarded.
+        pub fn wait_for_result(
+            self,
+            deadline: Option<Instant>,
+        ) -> std::result::Result<ReturnedValue, JniCacheError> {
+            match deadline {
+                Some(deadline) => {
+                    let timeout = deadline.saturating_duration_since(Instant::now());
+                    self.receiver.recv_timeout(timeout).map_err(|e| match e {
+                        kanal::ReceiveErrorTimeout::Timeout => JniCacheError::Timeout,
+                        _ => JniCacheError::ExecutorDisconnected,
+                    })?
+                }
+                None => self
+                    .receiver
+                    .recv()
+                    .map_err(|_| JniCacheError::ExecutorDisconnected)?,
+            }
+        }
+    }
+
+    /// Channel of a single call whose result is awaited instead of blocking the caller.
+    pub struct JVMAsyncResult {
+        receiver: kanal::AsyncReceiver<JVMResultMessage>,
+    }
+    impl JVMAsyncResult {
+        pub fn new() -> (JVMResultSender, Self) {
+            let (sender, receiver) = kanal::bounded(1);
+            (
+                JVMResultSender { sender },
+                Self {
+                    receiver: receiver.to_async(),
+                },
+            )
+        }
+
+        /// Resolves with Timeout once the deadline has passed, the deadline timer wakes the
+        /// task polling it. The receiver is dropped so a late answer is discarded.
+        pub async fn wait_for_result(
+            self,
+            deadline: Option<Instant>,
+        ) -> std::result::Result<ReturnedValue, JniCacheError> {
+            let mut received = pin!(self.receiver.recv());
+            let mut alarm: Option<Waker> = None;
+            poll_fn(|cx| {
+                if let Poll::Ready(message) = received.as_mut().poll(cx) {
+                    return Poll::Ready(message.map_err(|_| JniCacheError::ExecutorDisconnected));
+                }
+                let Some(deadline) = deadline else {
+                    return Poll::Pending;
+                };
+                if Instant::now() >= deadline {
+                    return Poll::Ready(Err(JniCacheError::Timeout));
+                }
+                if !alarm
+                    .as_ref()
+                    .is_some_and(|waker| waker.will_wake(cx.waker()))
+                {
+                    deadline_timer::wake_at(deadline, cx.waker().clone());
+                    alarm = Some(cx.waker().clone());
+                }
+                Poll::Pending
+            })
+            .await?
+        }
+    }
+
+    /// Thread waking the async waits at their deadline, started with the first one.
+    mod deadline_timer {
+        use std::cmp::{Ordering, Reverse};
+        use std::collections::BinaryHeap;
+        use std::sync::OnceLock;
+        use std::task::Waker;
+        use std::time::Instant;
+
+        struct Alarm {
+            deadline: Instant,
+            waker: Waker,
+        }
+
+        impl PartialEq for Alarm {
+            fn eq(&self, other: &Self) -> bool {
+                self.deadline == other.deadline
+            }
+        }
+        impl Eq for Alarm {}
+        impl PartialOrd for Alarm {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
             }
         }
-        pub fn get_sender(&self) -> JVMResultSender {
-            JVMResultSender {
-                sender: self.channel.0.clone(),
+        impl Ord for Alarm {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.deadline.cmp(&other.deadline)
             }
         }
 
-        pub fn wait_for_result(&self) -> std::result::Result<JVMResponseWrapper, ()> {
-            if let Ok((res, now)) = self.channel.1.recv() {
-                let result: JVMResponseWrapper = match res {
-                    ReturnedValue::I32(i) => JVMResponseWrapper::new(i),
-                    ReturnedValue::Long(v) => JVMResponseWrapper::new(v),
-                    ReturnedValue::String(s) => JVMResponseWrapper::new(s),
-                    ReturnedValue::VecUsize(u) => JVMResponseWrapper::new(u),
-                    _ => JVMResponseWrapper::new(()),
+        static ALARMS: OnceLock<kanal::Sender<Alarm>> = OnceLock::new();
+
+        pub fn wake_at(deadline: Instant, waker: Waker) {
+            let alarms = ALARMS.get_or_init(|| {
+                let (sender, receiver) = kanal::unbounded();
+                std::thread::Builder::new()
+                    .name("jvm-deadline-timer".to_string())
+                    .spawn(move || run(receiver))
+                    .expect("cannot spawn the deadline timer");
+                sender
+            });
+            let _ = alarms.send(Alarm { deadline, waker });
+        }
+
+        fn run(receiver: kanal::Receiver<Alarm>) {
+            let mut alarms: BinaryHeap<Reverse<Alarm>> = BinaryHeap::new();
+            loop {
+                let received = match alarms.peek() {
+                    Some(Reverse(next)) => {
+                        let timeout = next.deadline.saturating_duration_since(Instant::now());
+                        match receiver.recv_timeout(timeout) {
+                            Ok(alarm) => Some(alarm),
+                            Err(kanal::ReceiveErrorTimeout::Timeout) => None,
+                            Err(_) => return,
+                        }
+                    }
+                    None => match receiver.recv() {
+                        Ok(alarm) => Some(alarm),
+                        Err(_) => return,
+                    },
                 };
+                if let Some(alarm) = received {
+                    alarms.push(Reverse(alarm));
+                }
 
-                Ok(result)
-            } else {
-                Err(())
+                let now = Instant::now();
+                while alarms
+                    .peek()
+                    .is_some_and(|Reverse(alarm)| alarm.deadline <= now)
+                {
+                    if let Some(Reverse(alarm)) = alarms.pop() {
+                        alarm.waker.wake();
+                    }
+                }
             }
         }
     }
 
     #[derive(Debug)]
     pub struct JVMResultSender {
-        sender: kanal::Sender<(ReturnedValue, Instant)>,
+        sender: kanal::Sender<JVMResultMessage>,
     }
 
     impl JVMResultSender {
-        pub fn send(&self, value: ReturnedValue) -> std::result::Result<(), kanal::SendError> {
-            let now = std::time::Instant::now();
-            self.sender.send((value, now))
+        pub fn send(&self, value: JVMResultMessage) -> std::result::Result<(), kanal::SendError> {
+            self.sender.send(value)
         }
     }
 
-    pub struct JVMResponseWrapper {
-        inner: Box<dyn DynJVMResponse>,
+    /// A rust type the executor can answer with.
+    pub trait JVMResponse: Sized {
+        fn from_returned_value(value: ReturnedValue) -> std::result::Result<Self, JniCacheError>;
     }
 
-    impl JVMResponseWrapper {
-        fn new(value: impl JVMResponse + 'static) -> Self {
-            let value = Box::new(value);
-            JVMResponseWrapper { inner: value }
-        }
-
-        pub fn to_value<T: 'static + JVMResponse>(
-            &self,
-        ) -> std::result::Result<Box<T>, Box<dyn std::any::Any>> {
-            self.inner.get_value().downcast::<T>()
-        }
-    }
-    impl Deref for JVMResponseWrapper {
-        type Target = dyn DynJVMResponse;
-        fn deref(&self) -> &Self::Target {
-            &*self.inner
+    impl JVMResponse for ReturnedValue {
+        fn from_returned_value(value: ReturnedValue) -> std::result::Result<Self, JniCacheError> {
+            Ok(value)
         }
     }
 
-    pub trait DynJVMResponse {
-        fn get_value(&self) -> Box<dyn std::any::Any>;
+    macro_rules! impl_jvm_response {
+        ($($ty:ty => $variant:ident),+ $(,)?) => {
+            $(
+                impl TryFrom<ReturnedValue> for $ty {
+                    type Error = JniCacheError;
+
+                    fn try_from(value: ReturnedValue) -> std::result::Result<Self, Self::Error> {
+                        match value {
+                            ReturnedValue::$variant(v) => Ok(v.into()),
+                            other => Err(JniCacheError::ReturnTypeMismatch {
+                                expected: std::any::type_name::<$ty>(),
+                                found: other.variant_name().to_string(),
+                            }),
+                        }
+                    }
+                }
+
+                impl JVMResponse for $ty {
+                    fn from_returned_value(
+                        value: ReturnedValue,
+                    ) -> std::result::Result<Self, JniCacheError> {
+                        Self::try_from(value)
+                    }
+                }
+            )+
+        };
     }
 
-    impl<T: 'static + JVMResponse> DynJVMResponse for T
-    where
-        T::Item: 'static,
-    {
-        fn get_value(&self) -> Box<dyn std::any::Any> {
-            Box::new(JVMResponse::get_value(self))
+    impl_jvm_response!(
+        bool => Bool,
+        i8 => Byte,
+        u16 => Char,
+        i16 => Short,
+        i32 => I32,
+        i64 => Long,
+        f32 => Float,
+        f64 => Double,
+        String => String,
+        ObjectId => JObject,
+        Vec<f32> => VecFloat,
+        Vec<f64> => VecDouble,
+        Vec<i32> => VecI32,
+        Vec<i64> => VecLong,
+        Vec<u8> => VecU8,
+        Vec<bool> => VecBool,
+        Vec<i16> => VecShort,
+        Vec<u16> => VecChar,
+        Vec<Option<String>> => VecString,
+    );
+
+    impl TryFrom<ReturnedValue> for () {
+        type Error = JniCacheError;
+
+        fn try_from(value: ReturnedValue) -> std::result::Result<Self, Self::Error> {
+            match value {
+                ReturnedValue::Void => Ok(()),
+                other => Err(JniCacheError::ReturnTypeMismatch {
+                    expected: "()",
+                    found: other.variant_name().to_string(),
+                }),
+            }
         }
     }
-    pub trait JVMResponse {
-        type Item;
-        fn get_value(&self) -> Self::Item;
-    }
+
     impl JVMResponse for () {
-        type Item = ();
-        fn get_value(&self) -> Self::Item {
-            *self
+        fn from_returned_value(value: ReturnedValue) -> std::result::Result<Self, JniCacheError> {
+            Self::try_from(value)
         }
     }
 
-    impl JVMResponse for i64 {
-        type Item = i64;
-        fn get_value(&self) -> Self::Item {
-            *self
+    /// A null object or array is answered as None.
+    impl<T: JVMResponse> JVMResponse for Option<T> {
+        fn from_returned_value(value: ReturnedValue) -> std::result::Result<Self, JniCacheError> {
+            match value {
+                ReturnedValue::Null => Ok(None),
+                other => T::from_returned_value(other).map(Some),
+            }
         }
     }
-    impl JVMResponse for String {
-        type Item = String;
-        fn get_value(&self) -> Self::Item {
-            self.to_owned()
+}
+
+pub mod java_exception {
+    use jni::objects::{JObject, JObjectArray, JString};
+    use jni::JNIEnv;
+
+    use crate::jni_methods_cache::error::{JavaThrowable, JniCacheError};
+
+    const MAX_CAUSE_DEPTH: usize = 16;
+
+    /// Checks the env for a pending java exception. If one is found it is cleared and
+    /// returned as a JniCacheError::JavaException, so the env stays usable.
+    pub fn check_exception(env: &mut JNIEnv) -> std::result::Result<(), JniCacheError> {
+        if !env.exception_check()? {
+            return Ok(());
         }
+        let throwable = env.exception_occurred()?;
+        env.exception_clear()?;
+
+        let java_throwable = describe_throwable(env, &throwable, 0).unwrap_or_else(|_| {
+            let _ = env.exception_clear();
+            JavaThrowable {
+                class_name: "java.lang.Throwable".to_string(),
+                message: None,
+                stack_trace: vec![],
+                cause: None,
+            }
+        });
+        let _ = env.delete_local_ref(throwable);
+
+        Err(JniCacheError::JavaException(java_throwable))
     }
-    impl JVMResponse for i32 {
-        type Item = i32;
-        fn get_value(&self) -> Self::Item {
-            *self
+
+    fn describe_throwable(
+        env: &mut JNIEnv,
+        throwable: &JObject,
+        depth: usize,
+    ) -> jni::errors::Result<JavaThrowable> {
+        let class = env
+            .call_method(throwable, "getClass", "()Ljava/lang/Class;", &[])?
+            .l()?;
+        let class_name = object_to_string(env, &class, "getName")?.unwrap_or_default();
+        env.delete_local_ref(class)?;
+
+        let message = object_to_string(env, throwable, "getMessage")?;
+
+        let trace: JObjectArray = env
+            .call_method(
+                throwable,
+                "getStackTrace",
+                "()[Ljava/lang/StackTraceElement;",
+                &[],
+            )?
+            .l()?
+            .into();
+        let mut stack_trace = vec![];
+        for i in 0..env.get_array_length(&trace)? {
+            let element = env.get_object_array_element(&trace, i)?;
+            if let Some(line) = object_to_string(env, &element, "toString")? {
+                stack_trace.push(line);
+            }
+            env.delete_local_ref(element)?;
         }
+        env.delete_local_ref(trace)?;
+
+        let cause_obj = env
+            .call_method(throwable, "getCause", "()Ljava/lang/Throwable;", &[])?
+            .l()?;
+        let cause = if cause_obj.is_null()
+            || depth >= MAX_CAUSE_DEPTH
+            || env.is_same_object(&cause_obj, throwable)?
+        {
+            None
+        } else {
+            Some(Box::new(describe_throwable(env, &cause_obj, depth + 1)?))
+        };
+        env.delete_local_ref(cause_obj)?;
+
+        Ok(JavaThrowable {
+            class_name,
+            message,
+            stack_trace,
+            cause,
+        })
     }
-    impl JVMResponse for Vec<usize> {
-        type Item = Vec<usize>;
-        fn get_value(&self) -> Self::Item {
-            self.to_vec()
+
+    fn object_to_string(
+        env: &mut JNIEnv,
+        object: &JObject,
+        method_name: &str,
+    ) -> jni::errors::Result<Option<String>> {
+        let j_string: JString = env
+            .call_method(object, method_name, "()Ljava/lang/String;", &[])?
+            .l()?
+            .into();
+        if j_string.is_null() {
+            return Ok(None);
         }
+        let rust_string: String = env.get_string(&j_string)?.into();
+        env.delete_local_ref(j_string)?;
+        Ok(Some(rust_string))
     }
 }
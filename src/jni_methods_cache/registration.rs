@@ -0,0 +1,105 @@
+/***** Registration on the live cache ******/
+use crate::jni_methods_cache::error::JniCacheError;
+use crate::jni_methods_cache::executor::{Registered, Registration};
+use crate::jni_methods_cache::jni_cache::JniCache;
+use crate::jni_methods_cache::methods_cache::java_method_build_tools::{
+    check_typed_field, check_typed_signature,
+};
+use crate::jni_methods_cache::methods_cache::java_method_cache_utils::{
+    FieldHandle, FieldType, MethodHandle, MethodType,
+};
+use crate::jni_methods_cache::typed_method::{
+    Field, JavaArgList, JavaReturn, Method, StaticField, StaticMethod,
+};
+
+/// Same registrations as the JavaMethodCacheBuilder, on the built cache : they are
+/// resolved on the executor thread, so modules loaded after the init can add their own
+/// bindings. A class, method or field already cached keeps its handle. A typed binding
+/// whose types do not match its signature is returned as an error, where the builder
+/// panics.
+impl JniCache {
+    pub fn add_standard_class_name(&self, class_name: &str) -> Result<(), JniCacheError> {
+        self.register(Registration::StandardClass(class_name.to_string()))
+            .map(|_| ())
+    }
+
+    pub fn add_java_method(
+        &self,
+        method_type: MethodType,
+        class: &str,
+        method_name: &str,
+        signature: &str,
+    ) -> Result<MethodHandle, JniCacheError> {
+        match self.register(Registration::Method {
+            method_type,
+            class: class.to_string(),
+            name: method_name.to_string(),
+            sig: signature.to_string(),
+        })? {
+            Registered::Method(handle) => Ok(handle),
+            other => unreachable!("a method registration answered [{:?}]", other),
+        }
+    }
+
+    pub fn add_static_method<Args: JavaArgList, R: JavaReturn>(
+        &self,
+        class: &str,
+        method_name: &str,
+        signature: &str,
+    ) -> Result<StaticMethod<Args, R>, JniCacheError> {
+        check_typed_signature::<Args, R>(class, method_name, signature)?;
+        self.add_java_method(MethodType::Static, class, method_name, signature)
+            .map(StaticMethod::new)
+    }
+
+    pub fn add_method<Args: JavaArgList, R: JavaReturn>(
+        &self,
+        class: &str,
+        method_name: &str,
+        signature: &str,
+    ) -> Result<Method<Args, R>, JniCacheError> {
+        check_typed_signature::<Args, R>(class, method_name, signature)?;
+        self.add_java_method(MethodType::NonStatic, class, method_name, signature)
+            .map(Method::new)
+    }
+
+    pub fn add_java_field(
+        &self,
+        field_type: FieldType,
+        class: &str,
+        field_name: &str,
+        signature: &str,
+    ) -> Result<FieldHandle, JniCacheError> {
+        match self.register(Registration::Field {
+            field_type,
+            class: class.to_string(),
+            name: field_name.to_string(),
+            sig: signature.to_string(),
+        })? {
+            Registered::Field(handle) => Ok(handle),
+            other => unreachable!("a field registration answered [{:?}]", other),
+        }
+    }
+
+    pub fn add_static_field<T: JavaReturn>(
+        &self,
+        class: &str,
+        field_name: &str,
+        signature: &str,
+    ) -> Result<StaticField<T>, JniCacheError> {
+        check_typed_field::<T>(class, field_name, signature)?;
+        self.add_java_field(FieldType::Static, class, field_name, signature)
+            .map(StaticField::new)
+    }
+
+    pub fn add_field<T: JavaReturn>(
+        &self,
+        class: &str,
+        field_name: &str,
+        signature: &str,
+    ) -> Result<Field<T>, JniCacheError> {
+        check_typed_field::<T>(class, field_name, signature)?;
+        self.add_java_field(FieldType::NonStatic, class, field_name, signature)
+            .map(Field::new)
+    }
+}
@@ -0,0 +1,292 @@
+/***** Pool of executor threads attached to the jvm ******/
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+
+use jni::objects::JValue;
+use jni::JNIEnv;
+use parking_lot::Mutex;
+
+use crate::jni_methods_cache::config::JniCacheConfig;
+use crate::jni_methods_cache::error::JniCacheError;
+use crate::jni_methods_cache::executor::{self, ExecutorReceiver, JvmCallEvent};
+use crate::{JavaMethodCache, ObjectId};
+
+/// Number of calls queued on a worker or running on it.
+#[derive(Clone, Default)]
+pub struct WorkerLoad(Arc<AtomicUsize>);
+
+impl WorkerLoad {
+    pub fn get(&self) -> usize {
+        self.0.load(Ordering::Acquire)
+    }
+
+    fn add(&self) {
+        self.0.fetch_add(1, Ordering::AcqRel);
+    }
+
+    fn remove(&self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+
+    /// Held by the worker while it runs a call, the call is counted out when it is dropped,
+    /// from the worker load and from the calls on its stored object, if any.
+    pub fn running(&self, bound: Option<(ObjectRoutes, ObjectId)>) -> WorkerLoadGuard {
+        WorkerLoadGuard {
+            load: self.clone(),
+            bound,
+        }
+    }
+}
+
+pub struct WorkerLoadGuard {
+    load: WorkerLoad,
+    bound: Option<(ObjectRoutes, ObjectId)>,
+}
+
+impl Drop for WorkerLoadGuard {
+    fn drop(&mut self) {
+        self.load.remove();
+        if let Some((routes, receiver_id)) = &self.bound {
+            routes.release(receiver_id);
+        }
+    }
+}
+
+/// Route taken by the calls queued or running on a stored object, with their number.
+#[derive(Clone, Default)]
+pub struct ObjectRoutes(Arc<Mutex<HashMap<ObjectId, (Route, usize)>>>);
+
+impl ObjectRoutes {
+    /// Route of a new call on receiver_id : the one of its calls in flight if any, route
+    /// otherwise. The call is counted in until it is released.
+    fn bind(&self, receiver_id: &ObjectId, route: Route) -> Route {
+        let mut routes = self.0.lock();
+        let (bound, calls) = routes.entry(receiver_id.clone()).or_insert((route, 0));
+        *calls += 1;
+        *bound
+    }
+
+    /// Counts out a call on receiver_id, once it has none left its next call is free to
+    /// take its own route.
+    pub fn release(&self, receiver_id: &ObjectId) {
+        let mut routes = self.0.lock();
+        if let Some((_, calls)) = routes.get_mut(receiver_id) {
+            *calls -= 1;
+            if *calls == 0 {
+                routes.remove(receiver_id);
+            }
+        }
+    }
+}
+
+/// Caller side of the queue of a worker.
+#[derive(Clone)]
+pub struct WorkerQueue {
+    sender: kanal::Sender<JvmCallEvent>,
+    load: WorkerLoad,
+}
+
+impl WorkerQueue {
+    pub fn new(sender: kanal::Sender<JvmCallEvent>, load: WorkerLoad) -> Self {
+        Self { sender, load }
+    }
+}
+
+/// Where the calls of a JvmCaller are sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Route {
+    Pool,
+    /// Index of a named lane in the dispatcher.
+    Lane(usize),
+}
+
+/// Sends the calls to the workers of the pool, or to the thread of their lane.
+///
+/// In the pool, the calls on a stored object always go to the worker its id hashes to, so
+/// they run in the order they are sent ; the other calls go to the least loaded worker,
+/// skipping the ones whose queue is closed because they stopped. A lane has a single
+/// thread, its calls run in the order they are sent.
+///
+/// A call on a stored object whose previous calls are still queued or running follows
+/// them to their queue whatever its own route, so mixing the pool and the lanes on an
+/// object never reorders its calls.
+#[derive(Clone)]
+pub struct Dispatcher {
+    workers: Vec<WorkerQueue>,
+    lanes: Vec<(String, WorkerQueue)>,
+    routes: ObjectRoutes,
+}
+
+impl Dispatcher {
+    pub fn new(
+        workers: Vec<WorkerQueue>,
+        lanes: Vec<(String, WorkerQueue)>,
+        routes: ObjectRoutes,
+    ) -> Self {
+        Self {
+            workers,
+            lanes,
+            routes,
+        }
+    }
+
+    pub fn lane(&self, name: &str) -> Option<Route> {
+        self.lanes
+            .iter()
+            .position(|(lane, _)| lane == name)
+            .map(Route::Lane)
+    }
+
+    pub fn send(&self, route: Route, event: JvmCallEvent) -> Result<(), kanal::SendError> {
+        let receiver_id = event.receiver_id().cloned();
+        let route = match &receiver_id {
+            Some(receiver_id) => self.routes.bind(receiver_id, route),
+            None => route,
+        };
+        let worker = match (route, &receiver_id) {
+            (Route::Lane(index), _) => &self.lanes[index].1,
+            (Route::Pool, Some(receiver_id)) => {
+                let mut hasher = DefaultHasher::new();
+                receiver_id.hash(&mut hasher);
+                &self.workers[hasher.finish() as usize % self.workers.len()]
+            }
+            (Route::Pool, None) => self
+                .workers
+                .iter()
+                .filter(|worker| !worker.sender.is_disconnected())
+                .min_by_key(|worker| worker.load.get())
+                .ok_or(kanal::SendError::Closed)?,
+        };
+
+        worker.load.add();
+        worker.sender.send(event).inspect_err(|_| {
+            worker.load.remove();
+            if let Some(receiver_id) = &receiver_id {
+                self.routes.release(receiver_id);
+            }
+        })
+    }
+}
+
+/// Spawns one worker per receiver, each one attached to the jvm with its own JNIEnv and
+/// named after the thread name of the config and its suffix : its index in the pool or
+/// its lane. The first worker builds the cache, the other ones are started once it is
+/// built and share it. Returns once the cache is built ; if the build fails, every queue
+/// is closed with the first worker and the failure is returned as BuildFailed.
+pub fn spawn_workers(
+    java_vm: Arc<jni::JavaVM>,
+    cache: Arc<OnceLock<JavaMethodCache>>,
+    config: JniCacheConfig,
+    receivers: Vec<(String, ExecutorReceiver)>,
+    build: impl FnOnce(&mut JNIEnv) -> JavaMethodCache + Send + 'static,
+) -> Result<(), JniCacheError> {
+    let mut receivers = receivers.into_iter();
+    let (suffix, first_receiver) = receivers.next().expect("the executor pool has no worker");
+    let name = worker_name(&config, &suffix);
+    let worker = name.clone();
+    let (built_sender, built) = kanal::bounded(1);
+
+    worker_builder(&config, &name)
+        .spawn(move || {
+            let mut env = attach_worker(&java_vm, &worker);
+            let java_method_cache =
+                match std::panic::catch_unwind(AssertUnwindSafe(|| build(&mut env))) {
+                    Ok(java_method_cache) => cache.get_or_init(|| java_method_cache),
+                    Err(payload) => {
+                        let _ = built_sender.send(Err(panic_message(payload.as_ref())));
+                        return;
+                    }
+                };
+
+            for (suffix, receiver) in receivers {
+                let java_vm = java_vm.clone();
+                let cache = cache.clone();
+                let config = config.clone();
+                let name = worker_name(&config, &suffix);
+                let worker = name.clone();
+                worker_builder(&config, &name)
+                    .spawn(move || {
+                        let mut env = attach_worker(&java_vm, &worker);
+                        let java_method_cache = cache
+                            .get()
+                            .expect("the cache is built before the workers are started");
+                        executor::run(receiver, &mut env, java_method_cache, &config);
+                    })
+                    .unwrap_or_else(|e| {
+                        panic!("cannot spawn the executor worker [{}]: {}", name, e)
+                    });
+            }
+
+            let _ = built_sender.send(Ok(()));
+            executor::run(first_receiver, &mut env, java_method_cache, &config);
+        })
+        .unwrap_or_else(|e| panic!("cannot spawn the executor worker [{}]: {}", name, e));
+
+    match built.recv() {
+        Ok(result) => result.map_err(JniCacheError::BuildFailed),
+        Err(_) => Err(JniCacheError::BuildFailed(format!(
+            "the executor worker [{}] stopped before the cache was built",
+            name
+        ))),
+    }
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    match payload.downcast_ref::<&str>() {
+        Some(message) => message.to_string(),
+        None => payload
+            .downcast_ref::<String>()
+            .cloned()
+            .unwrap_or_else(|| "the build panicked".to_string()),
+    }
+}
+
+fn worker_name(config: &JniCacheConfig, suffix: &str) -> String {
+    format!("{}-{}", config.thread_name, suffix)
+}
+
+fn worker_builder(config: &JniCacheConfig, name: &str) -> std::thread::Builder {
+    let builder = std::thread::Builder::new().name(name.to_string());
+    match config.stack_size {
+        Some(stack_size) => builder.stack_size(stack_size),
+        None => builder,
+    }
+}
+
+/// The java side of the thread is given the same name, it shows up in java stack traces.
+fn attach_worker<'a>(java_vm: &'a jni::JavaVM, name: &str) -> JNIEnv<'a> {
+    let mut env = java_vm
+        .attach_current_thread_permanently()
+        .expect("cannot attach the executor thread to the jvm");
+
+    if let Err(e) = set_java_thread_name(&mut env, name) {
+        let _ = env.exception_clear();
+        log::warn!("cannot name the java thread of [{}]: {:?}", name, e);
+    }
+    env
+}
+
+fn set_java_thread_name(env: &mut JNIEnv, name: &str) -> jni::errors::Result<()> {
+    env.with_local_frame(4, |env| {
+        let thread = env
+            .call_static_method(
+                "java/lang/Thread",
+                "currentThread",
+                "()Ljava/lang/Thread;",
+                &[],
+            )?
+            .l()?;
+        let name = env.new_string(name)?;
+        env.call_method(
+            thread,
+            "setName",
+            "(Ljava/lang/String;)V",
+            &[JValue::Object(&name)],
+        )?;
+        Ok(())
+    })
+}
@@ -0,0 +1,460 @@
+/***** Typed method and field handles ******/
+use std::future::Future;
+use std::marker::PhantomData;
+
+use jni::signature::{JavaType, Primitive};
+
+use crate::jni_methods_cache::error::JniCacheError;
+use crate::jni_methods_cache::jni_cache::JniCache;
+use crate::jni_methods_cache::methods_cache::java_method_cache_utils::{
+    FieldHandle, JavaArgs, MethodHandle, ObjectId,
+};
+use crate::jni_methods_cache::methods_cache::JVMResponse;
+
+/// A rust value that can be passed as a single java argument.
+pub trait IntoJavaArg {
+    /// Java type of the argument, an ObjectId stands for any java object.
+    fn java_type() -> JavaType;
+    fn into_java_arg(self) -> JavaArgs;
+}
+
+impl IntoJavaArg for bool {
+    fn java_type() -> JavaType {
+        JavaType::Primitive(Primitive::Boolean)
+    }
+    fn into_java_arg(self) -> JavaArgs {
+        JavaArgs::Bool(self)
+    }
+}
+
+impl IntoJavaArg for i8 {
+    fn java_type() -> JavaType {
+        JavaType::Primitive(Primitive::Byte)
+    }
+    fn into_java_arg(self) -> JavaArgs {
+        JavaArgs::Byte(self)
+    }
+}
+
+impl IntoJavaArg for u16 {
+    fn java_type() -> JavaType {
+        JavaType::Primitive(Primitive::Char)
+    }
+    fn into_java_arg(self) -> JavaArgs {
+        JavaArgs::Char(self)
+    }
+}
+
+impl IntoJavaArg for i16 {
+    fn java_type() -> JavaType {
+        JavaType::Primitive(Primitive::Short)
+    }
+    fn into_java_arg(self) -> JavaArgs {
+        JavaArgs::Short(self)
+    }
+}
+
+impl IntoJavaArg for i32 {
+    fn java_type() -> JavaType {
+        JavaType::Primitive(Primitive::Int)
+    }
+    fn into_java_arg(self) -> JavaArgs {
+        JavaArgs::I32(self)
+    }
+}
+
+impl IntoJavaArg for i64 {
+    fn java_type() -> JavaType {
+        JavaType::Primitive(Primitive::Long)
+    }
+    fn into_java_arg(self) -> JavaArgs {
+        JavaArgs::Long(self)
+    }
+}
+
+impl IntoJavaArg for f32 {
+    fn java_type() -> JavaType {
+        JavaType::Primitive(Primitive::Float)
+    }
+    fn into_java_arg(self) -> JavaArgs {
+        JavaArgs::Float(self)
+    }
+}
+
+impl IntoJavaArg for f64 {
+    fn java_type() -> JavaType {
+        JavaType::Primitive(Primitive::Double)
+    }
+    fn into_java_arg(self) -> JavaArgs {
+        JavaArgs::Double(self)
+    }
+}
+
+impl IntoJavaArg for String {
+    fn java_type() -> JavaType {
+        string_type()
+    }
+    fn into_java_arg(self) -> JavaArgs {
+        JavaArgs::String(self)
+    }
+}
+
+impl IntoJavaArg for &str {
+    fn java_type() -> JavaType {
+        string_type()
+    }
+    fn into_java_arg(self) -> JavaArgs {
+        JavaArgs::String(self.to_string())
+    }
+}
+
+impl IntoJavaArg for ObjectId {
+    fn java_type() -> JavaType {
+        object_type()
+    }
+    fn into_java_arg(self) -> JavaArgs {
+        JavaArgs::JObject(self)
+    }
+}
+
+/// A tuple of rust values forming the argument list of a java method.
+pub trait JavaArgList {
+    fn java_types() -> Vec<JavaType>;
+    fn into_java_args(self) -> JavaArgs;
+}
+
+impl JavaArgList for () {
+    fn java_types() -> Vec<JavaType> {
+        vec![]
+    }
+    fn into_java_args(self) -> JavaArgs {
+        JavaArgs::None
+    }
+}
+
+/// A rust type a java method can return.
+pub trait JavaReturn: JVMResponse + 'static {
+    /// Java type of the value, an ObjectId stands for any java object.
+    fn java_type() -> JavaType;
+}
+
+pub(crate) fn string_type() -> JavaType {
+    JavaType::Object("java/lang/String".to_string())
+}
+
+pub(crate) fn object_type() -> JavaType {
+    JavaType::Object("java/lang/Object".to_string())
+}
+
+fn array_of(element: JavaType) -> JavaType {
+    JavaType::Array(Box::new(element))
+}
+
+macro_rules! impl_java_return {
+    ($($ty:ty => $java_type:expr),+ $(,)?) => {
+        $(
+            impl JavaReturn for $ty {
+                fn java_type() -> JavaType {
+                    $java_type
+                }
+            }
+        )+
+    };
+}
+
+impl_java_return!(
+    () => JavaType::Primitive(Primitive::Void),
+    bool => JavaType::Primitive(Primitive::Boolean),
+    i8 => JavaType::Primitive(Primitive::Byte),
+    u16 => JavaType::Primitive(Primitive::Char),
+    i16 => JavaType::Primitive(Primitive::Short),
+    i32 => JavaType::Primitive(Primitive::Int),
+    i64 => JavaType::Primitive(Primitive::Long),
+    f32 => JavaType::Primitive(Primitive::Float),
+    f64 => JavaType::Primitive(Primitive::Double),
+    String => string_type(),
+    ObjectId => object_type(),
+    Vec<f32> => array_of(JavaType::Primitive(Primitive::Float)),
+    Vec<f64> => array_of(JavaType::Primitive(Primitive::Double)),
+    Vec<i32> => array_of(JavaType::Primitive(Primitive::Int)),
+    Vec<i64> => array_of(JavaType::Primitive(Primitive::Long)),
+    Vec<u8> => array_of(JavaType::Primitive(Primitive::Byte)),
+    Vec<bool> => array_of(JavaType::Primitive(Primitive::Boolean)),
+    Vec<i16> => array_of(JavaType::Primitive(Primitive::Short)),
+    Vec<u16> => array_of(JavaType::Primitive(Primitive::Char)),
+    Vec<Option<String>> => array_of(string_type()),
+);
+
+/// A method or field that can be null, read with the java type of the value.
+impl<T: JavaReturn> JavaReturn for Option<T> {
+    fn java_type() -> JavaType {
+        T::java_type()
+    }
+}
+
+/// Whether a value of the rust_type can be passed as, or read from, the declared java
+/// type. An ObjectId goes with any object or array, its class is checked on the call.
+pub(crate) fn is_typed_as(declared: &JavaType, rust_type: &JavaType) -> bool {
+    let is_reference =
+        |java_type: &JavaType| matches!(java_type, JavaType::Object(_) | JavaType::Array(_));
+    declared == rust_type || (*rust_type == object_type() && is_reference(declared))
+}
+
+/// Whether a rust argument of the rust_type can be passed where the declared java type
+/// is expected : a String also goes to a CharSequence or an Object parameter. Primitives
+/// are not boxed, they never go to an Object parameter.
+pub(crate) fn is_passable_as(declared: &JavaType, rust_type: &JavaType) -> bool {
+    let string_super_type = |java_type: &JavaType| match java_type {
+        JavaType::Object(class) => {
+            matches!(
+                class.as_str(),
+                "java/lang/CharSequence" | "java/lang/Object"
+            )
+        }
+        _ => false,
+    };
+    is_typed_as(declared, rust_type) || (*rust_type == string_type() && string_super_type(declared))
+}
+
+/// Handle of a cached static method, typed with its arguments and return value.
+pub struct StaticMethod<Args, R> {
+    handle: MethodHandle,
+    _types: PhantomData<fn(Args) -> R>,
+}
+
+/// Handle of a cached instance method, typed with its arguments and return value.
+pub struct Method<Args, R> {
+    handle: MethodHandle,
+    _types: PhantomData<fn(Args) -> R>,
+}
+
+impl<Args, R> StaticMethod<Args, R> {
+    pub(crate) fn new(handle: MethodHandle) -> Self {
+        Self {
+            handle,
+            _types: PhantomData,
+        }
+    }
+    pub fn handle(&self) -> MethodHandle {
+        self.handle
+    }
+}
+
+impl<Args, R> Method<Args, R> {
+    pub(crate) fn new(handle: MethodHandle) -> Self {
+        Self {
+            handle,
+            _types: PhantomData,
+        }
+    }
+    pub fn handle(&self) -> MethodHandle {
+        self.handle
+    }
+}
+
+impl<Args, R> Clone for StaticMethod<Args, R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<Args, R> Copy for StaticMethod<Args, R> {}
+
+impl<Args, R> Clone for Method<Args, R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<Args, R> Copy for Method<Args, R> {}
+
+impl<R: JavaReturn> StaticMethod<(), R> {
+    pub fn call(&self, cache: &JniCache) -> Result<R, JniCacheError> {
+        cache.call_static_method_by_handle::<R>(self.handle, JavaArgs::None, None)
+    }
+
+    pub fn call_async(
+        &self,
+        cache: &JniCache,
+    ) -> impl Future<Output = Result<R, JniCacheError>> + Send + 'static {
+        cache.call_static_method_by_handle_async::<R>(self.handle, JavaArgs::None, None)
+    }
+}
+
+impl<R: JavaReturn> Method<(), R> {
+    pub fn call(
+        &self,
+        cache: &JniCache,
+        receiver_id: impl Into<ObjectId>,
+    ) -> Result<R, JniCacheError> {
+        cache.call_method_by_handle::<R>(receiver_id, self.handle, JavaArgs::None, None)
+    }
+
+    pub fn call_async(
+        &self,
+        cache: &JniCache,
+        receiver_id: impl Into<ObjectId>,
+    ) -> impl Future<Output = Result<R, JniCacheError>> + Send + 'static {
+        cache.call_method_by_handle_async::<R>(receiver_id, self.handle, JavaArgs::None, None)
+    }
+}
+
+/// Handle of a cached static field, typed with its value.
+pub struct StaticField<T> {
+    handle: FieldHandle,
+    _type: PhantomData<fn() -> T>,
+}
+
+/// Handle of a cached instance field, typed with its value.
+pub struct Field<T> {
+    handle: FieldHandle,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<T> StaticField<T> {
+    pub(crate) fn new(handle: FieldHandle) -> Self {
+        Self {
+            handle,
+            _type: PhantomData,
+        }
+    }
+    pub fn handle(&self) -> FieldHandle {
+        self.handle
+    }
+}
+
+impl<T> Field<T> {
+    pub(crate) fn new(handle: FieldHandle) -> Self {
+        Self {
+            handle,
+            _type: PhantomData,
+        }
+    }
+    pub fn handle(&self) -> FieldHandle {
+        self.handle
+    }
+}
+
+impl<T> Clone for StaticField<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for StaticField<T> {}
+
+impl<T> Clone for Field<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for Field<T> {}
+
+impl<T: JavaReturn> StaticField<T> {
+    pub fn get(&self, cache: &JniCache) -> Result<T, JniCacheError> {
+        cache.get_static_field::<T>(self.handle, None)
+    }
+}
+
+impl<T: JavaReturn + IntoJavaArg> StaticField<T> {
+    pub fn set(&self, cache: &JniCache, value: T) -> Result<(), JniCacheError> {
+        cache.set_static_field(self.handle, value.into_java_arg())
+    }
+}
+
+impl<T: JavaReturn> Field<T> {
+    pub fn get(
+        &self,
+        cache: &JniCache,
+        receiver_id: impl Into<ObjectId>,
+    ) -> Result<T, JniCacheError> {
+        cache.get_field::<T>(receiver_id, self.handle, None)
+    }
+}
+
+impl<T: JavaReturn + IntoJavaArg> Field<T> {
+    pub fn set(
+        &self,
+        cache: &JniCache,
+        receiver_id: impl Into<ObjectId>,
+        value: T,
+    ) -> Result<(), JniCacheError> {
+        cache.set_field(receiver_id, self.handle, value.into_java_arg())
+    }
+}
+
+macro_rules! impl_typed_call {
+    ($($arg:ident: $ty:ident),+) => {
+        impl<$($ty: IntoJavaArg),+> JavaArgList for ($($ty,)+) {
+            fn java_types() -> Vec<JavaType> {
+                vec![$($ty::java_type()),+]
+            }
+            fn into_java_args(self) -> JavaArgs {
+                let ($($arg,)+) = self;
+                JavaArgs::Array(vec![$($arg.into_java_arg()),+])
+            }
+        }
+
+        impl<$($ty: IntoJavaArg,)+ R: JavaReturn> StaticMethod<($($ty,)+), R> {
+            #[allow(clippy::too_many_arguments)]
+            pub fn call(&self, cache: &JniCache, $($arg: $ty),+) -> Result<R, JniCacheError> {
+                cache.call_static_method_by_handle::<R>(
+                    self.handle,
+                    ($($arg,)+).into_java_args(),
+                    None,
+                )
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn call_async(
+                &self,
+                cache: &JniCache,
+                $($arg: $ty),+
+            ) -> impl Future<Output = Result<R, JniCacheError>> + Send + 'static {
+                cache.call_static_method_by_handle_async::<R>(
+                    self.handle,
+                    ($($arg,)+).into_java_args(),
+                    None,
+                )
+            }
+        }
+
+        impl<$($ty: IntoJavaArg,)+ R: JavaReturn> Method<($($ty,)+), R> {
+            #[allow(clippy::too_many_arguments)]
+            pub fn call(
+                &self,
+                cache: &JniCache,
+                receiver_id: impl Into<ObjectId>,
+                $($arg: $ty),+
+            ) -> Result<R, JniCacheError> {
+                cache.call_method_by_handle::<R>(
+                    receiver_id,
+                    self.handle,
+                    ($($arg,)+).into_java_args(),
+                    None,
+                )
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn call_async(
+                &self,
+                cache: &JniCache,
+                receiver_id: impl Into<ObjectId>,
+                $($arg: $ty),+
+            ) -> impl Future<Output = Result<R, JniCacheError>> + Send + 'static {
+                cache.call_method_by_handle_async::<R>(
+                    receiver_id,
+                    self.handle,
+                    ($($arg,)+).into_java_args(),
+                    None,
+                )
+            }
+        }
+    };
+}
+
+impl_typed_call!(a: A);
+impl_typed_call!(a: A, b: B);
+impl_typed_call!(a: A, b: B, c: C);
+impl_typed_call!(a: A, b: B, c: C, d: D);
+impl_typed_call!(a: A, b: B, c: C, d: D, e: E);
+impl_typed_call!(a: A, b: B, c: C, d: D, e: E, f: F);
+impl_typed_call!(a: A, b: B, c: C, d: D, e: E, f: F, g: G);
+impl_typed_call!(a: A, b: B, c: C, d: D, e: E, f: F, g: G, h: H);
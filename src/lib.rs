@@ -1,12 +1,20 @@
 mod jni_methods_cache;
+#[cfg(target_os = "android")]
 mod platform;
 
-pub use crate::jni_methods_cache::call_java_method;
-pub use crate::jni_methods_cache::call_java_static_method;
+pub use crate::jni_methods_cache::config::JniCacheConfig;
+pub use crate::jni_methods_cache::error::{JavaThrowable, JniCacheError};
+pub use crate::jni_methods_cache::jni_cache::{JniCache, StoredObject};
+pub use crate::jni_methods_cache::typed_method::{
+    Field, IntoJavaArg, JavaArgList, JavaReturn, Method, StaticField, StaticMethod,
+};
 pub use jni::signature;
 pub use jni_methods_cache::methods_cache::{
-    java_method_cache_utils::{JavaArgs, MethodType, ReturnedValue},
-    JavaMethodCache, JavaMethods,
+    java_method_cache_utils::{
+        FieldHandle, FieldType, JavaArgs, JavaFieldRef, JavaMethodRef, MethodHandle, MethodType,
+        ObjectId, ReturnedValue,
+    },
+    JavaMethodCache, JavaMethodCacheBuilder,
 };
 pub use signature::Primitive as JniPrimitive;
 pub use signature::ReturnType;
@@ -14,10 +22,17 @@ pub use signature::ReturnType;
 pub mod prelude {
     use super::*;
 
-    pub use jni_methods_cache::methods_cache::{
-        java_method_cache_utils::{JavaArgs, MethodType, ReturnedValue},
-        JavaMethodCache, JavaMethods,
+    pub use crate::jni_methods_cache::config::JniCacheConfig;
+    pub use crate::jni_methods_cache::error::{JavaThrowable, JniCacheError};
+    pub use crate::jni_methods_cache::jni_cache::{JniCache, StoredObject};
+    pub use crate::jni_methods_cache::methods_cache::{
+        java_method_cache_utils::{
+            FieldHandle, FieldType, JavaArgs, JavaFieldRef, JavaMethodRef, MethodHandle,
+            MethodType, ObjectId, ReturnedValue,
+        },
+        JavaMethodCache, JavaMethodCacheBuilder,
     };
+    pub use crate::jni_methods_cache::typed_method::{Field, Method, StaticField, StaticMethod};
     pub use signature::Primitive as JniPrimitive;
     pub use signature::ReturnType;
 }
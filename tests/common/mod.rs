@@ -0,0 +1,218 @@
+//! Embedded desktop JVM shared by the integration tests.
+//!
+//! The fixture classes of tests/fixtures are compiled with javac, a JVM is started
+//! through the invocation api and the cache is built once for the whole test binary.
+// every test binary uses its own part of the harness
+#![allow(dead_code)]
+use std::future::Future;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::pin::pin;
+use std::process::Command;
+use std::sync::{mpsc, Arc, Mutex, MutexGuard, OnceLock};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::Thread;
+use std::time::Duration;
+
+use jni::{InitArgsBuilder, JNIVersion, JavaVM};
+use jni_methods_cache::{
+    Field, JniCache, JniCacheConfig, Method, MethodHandle, MethodType, ObjectId, StaticField,
+    StaticMethod,
+};
+
+pub const CALCULATOR: &str = "quickjnicache/fixtures/Calculator";
+pub const COUNTER: &str = "quickjnicache/fixtures/Counter";
+
+pub type MixArgs = (bool, i8, u16, i16, i64, f32, f64);
+
+pub struct Fixture {
+    pub cache: JniCache,
+    methods: Methods,
+}
+
+impl Deref for Fixture {
+    type Target = Methods;
+
+    fn deref(&self) -> &Methods {
+        &self.methods
+    }
+}
+
+pub struct Methods {
+    pub add: StaticMethod<(i32, i32), i32>,
+    pub mix: StaticMethod<MixArgs, i64>,
+    pub half: StaticMethod<(f64,), f64>,
+    pub is_even: StaticMethod<(i32,), bool>,
+    pub greet: StaticMethod<(String,), String>,
+    pub fail: StaticMethod<(String,), ()>,
+    pub token: MethodHandle,
+    pub ints: StaticMethod<(), Vec<i32>>,
+    pub doubles: StaticMethod<(), Vec<f64>>,
+    pub floats: StaticMethod<(), Vec<f32>>,
+    pub longs: StaticMethod<(), Vec<i64>>,
+    pub bytes: StaticMethod<(), Vec<u8>>,
+    pub booleans: StaticMethod<(), Vec<bool>>,
+    pub shorts: StaticMethod<(), Vec<i16>>,
+    pub chars: StaticMethod<(), Vec<u16>>,
+    pub strings: StaticMethod<(), Vec<Option<String>>>,
+    pub create_counter: MethodHandle,
+    pub new_counter: MethodHandle,
+    pub describe_counter: StaticMethod<(ObjectId,), String>,
+    pub increment: Method<(i32,), i32>,
+    pub get: Method<(), i32>,
+    pub version: StaticField<i32>,
+    pub label: StaticField<String>,
+    pub counter_value: Field<i32>,
+}
+
+static FIXTURE: OnceLock<Mutex<Fixture>> = OnceLock::new();
+
+/// Locks the shared fixture, the tests run one at a time on the executor.
+pub fn fixture() -> MutexGuard<'static, Fixture> {
+    FIXTURE
+        .get_or_init(|| Mutex::new(start()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Starts the jvm of the test binary, with the compiled fixtures in its class path.
+pub fn start_jvm() -> JavaVM {
+    let classes_dir = compile_fixtures();
+
+    let jvm_args = InitArgsBuilder::new()
+        .version(JNIVersion::V8)
+        .option(format!("-Djava.class.path={}", classes_dir.display()))
+        .option("-Djava.awt.headless=true")
+        // aborts on a misused JNIEnv, and warns when a call outgrows its local frame
+        .option("-Xcheck:jni")
+        .build()
+        .expect("invalid jvm init args");
+    JavaVM::new(jvm_args).expect("cannot start the jvm")
+}
+
+fn start() -> Fixture {
+    let java_vm = start_jvm();
+
+    let (sender, receiver) = mpsc::channel();
+    // a small frame, the calls creating more local refs grow it
+    let config = JniCacheConfig {
+        local_frame_capacity: 4,
+        ..Default::default()
+    };
+    let cache = JniCache::init_with_config(java_vm, None, config, move |builder| {
+        for class_name in [
+            "java/lang/String",
+            "[I",
+            "[D",
+            "[F",
+            "[J",
+            "[B",
+            "[Z",
+            "[S",
+            "[C",
+            "[Ljava/lang/String;",
+        ] {
+            builder.add_standard_class_name(class_name);
+        }
+
+        let methods = Methods {
+            add: builder.add_static_method(CALCULATOR, "add", "(II)I"),
+            mix: builder.add_static_method(CALCULATOR, "mix", "(ZBCSJFD)J"),
+            half: builder.add_static_method(CALCULATOR, "half", "(D)D"),
+            is_even: builder.add_static_method(CALCULATOR, "isEven", "(I)Z"),
+            greet: builder.add_static_method(
+                CALCULATOR,
+                "greet",
+                "(Ljava/lang/String;)Ljava/lang/String;",
+            ),
+            fail: builder.add_static_method(CALCULATOR, "fail", "(Ljava/lang/String;)V"),
+            token: builder.add_java_method(
+                MethodType::Static,
+                CALCULATOR,
+                "token",
+                "()Ljava/lang/Object;",
+            ),
+            ints: builder.add_static_method(CALCULATOR, "ints", "()[I"),
+            doubles: builder.add_static_method(CALCULATOR, "doubles", "()[D"),
+            floats: builder.add_static_method(CALCULATOR, "floats", "()[F"),
+            longs: builder.add_static_method(CALCULATOR, "longs", "()[J"),
+            bytes: builder.add_static_method(CALCULATOR, "bytes", "()[B"),
+            booleans: builder.add_static_method(CALCULATOR, "booleans", "()[Z"),
+            shorts: builder.add_static_method(CALCULATOR, "shorts", "()[S"),
+            chars: builder.add_static_method(CALCULATOR, "chars", "()[C"),
+            strings: builder.add_static_method(CALCULATOR, "strings", "()[Ljava/lang/String;"),
+            create_counter: builder.add_java_method(
+                MethodType::Static,
+                COUNTER,
+                "create",
+                "(I)Lquickjnicache/fixtures/Counter;",
+            ),
+            new_counter: builder.add_java_method(
+                MethodType::Constructor,
+                COUNTER,
+                "<init>",
+                "(I)V",
+            ),
+            describe_counter: builder.add_static_method(
+                COUNTER,
+                "describe",
+                "(Lquickjnicache/fixtures/Counter;)Ljava/lang/String;",
+            ),
+            increment: builder.add_method(COUNTER, "increment", "(I)I"),
+            get: builder.add_method(COUNTER, "get", "()I"),
+            version: builder.add_static_field(CALCULATOR, "VERSION", "I"),
+            label: builder.add_static_field(CALCULATOR, "label", "Ljava/lang/String;"),
+            counter_value: builder.add_field(COUNTER, "value", "I"),
+        };
+        sender.send(methods).expect("test harness is gone");
+    })
+    .expect("cannot build the cache");
+
+    let methods = receiver
+        .recv_timeout(Duration::from_secs(30))
+        .expect("the cache builder was not called");
+    Fixture { cache, methods }
+}
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Minimal executor polling the future on the current thread, no async runtime needed.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+    let mut context = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => std::thread::park(),
+        }
+    }
+}
+
+fn compile_fixtures() -> PathBuf {
+    let fixtures_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let classes_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("fixture-classes");
+    std::fs::create_dir_all(&classes_dir).expect("cannot create the classes dir");
+
+    let sources: Vec<PathBuf> = std::fs::read_dir(fixtures_dir.join("quickjnicache/fixtures"))
+        .expect("no fixture sources")
+        .map(|entry| entry.expect("unreadable fixture").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "java"))
+        .collect();
+
+    let status = Command::new("javac")
+        .arg("-d")
+        .arg(&classes_dir)
+        .args(&sources)
+        .status()
+        .expect("javac is required to run the integration tests");
+    assert!(status.success(), "fixture compilation failed");
+
+    classes_dir
+}
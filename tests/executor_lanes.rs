@@ -0,0 +1,181 @@
+mod common;
+
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use common::{block_on, start_jvm, CALCULATOR, COUNTER};
+use jni_methods_cache::{JavaArgs, JniCache, JniCacheConfig, JniCacheError, ObjectId};
+
+static CACHE: OnceLock<JniCache> = OnceLock::new();
+
+/// Own test binary : the cache of this process has a pool of two workers and two lanes.
+fn cache_with_lanes() -> JniCache {
+    CACHE.get_or_init(start).clone()
+}
+
+fn start() -> JniCache {
+    let config = JniCacheConfig {
+        pool_size: 2,
+        lanes: vec!["ui".to_string(), "background".to_string()],
+        thread_name: "jvm".to_string(),
+        ..Default::default()
+    };
+    JniCache::init_with_config(start_jvm(), None, config, |builder| {
+        builder.add_standard_class_name("java/lang/String");
+    })
+    .expect("cannot build the cache")
+}
+
+fn thread_name_async(
+    cache: &JniCache,
+    millis: i32,
+) -> impl std::future::Future<Output = Result<String, JniCacheError>> {
+    cache.call_static_method_async::<String>(
+        CALCULATOR,
+        "threadName",
+        "(I)Ljava/lang/String;",
+        JavaArgs::I32(millis),
+        None,
+    )
+}
+
+fn increment_async(
+    cache: &JniCache,
+    counter: &str,
+    step: i32,
+) -> impl std::future::Future<Output = Result<i32, JniCacheError>> {
+    cache.call_method_async::<i32>(
+        ObjectId::from(counter),
+        COUNTER,
+        "increment",
+        "(I)I",
+        JavaArgs::I32(step),
+        None,
+    )
+}
+
+/// Increment that sleeps first, a call sent after it and run elsewhere would overtake it.
+fn increment_after_async(
+    cache: &JniCache,
+    counter: &str,
+    step: i32,
+    millis: i32,
+) -> impl std::future::Future<Output = Result<i32, JniCacheError>> {
+    cache.call_method_async::<i32>(
+        ObjectId::from(counter),
+        COUNTER,
+        "incrementAfter",
+        "(II)I",
+        JavaArgs::Array(vec![JavaArgs::I32(step), JavaArgs::I32(millis)]),
+        None,
+    )
+}
+
+#[test]
+fn lanes_run_their_calls_in_order_on_their_own_thread() {
+    let cache = cache_with_lanes();
+    let ui = cache.on_lane("ui").unwrap();
+    let background = cache.on_lane("background").unwrap();
+    assert!(matches!(
+        cache.on_lane("audio"),
+        Err(JniCacheError::UnknownLane(lane)) if lane == "audio"
+    ));
+
+    // a burst of slow background calls does not delay the ui lane
+    let burst: Vec<_> = (0..3)
+        .map(|_| thread_name_async(&background, 300))
+        .collect();
+    let ui_name = block_on(thread_name_async(
+        &ui.with_timeout(Duration::from_millis(250)),
+        0,
+    ));
+    assert_eq!(ui_name.unwrap(), "jvm-ui");
+    for name in burst {
+        assert_eq!(block_on(name).unwrap(), "jvm-background");
+    }
+
+    // a lane runs its calls in the order they are sent
+    cache
+        .new_object(
+            COUNTER,
+            "(I)V",
+            JavaArgs::I32(0),
+            Some("lane counter".into()),
+        )
+        .unwrap();
+    let increments: Vec<_> = (1..=20)
+        .map(|step| increment_async(&background, "lane counter", step))
+        .collect();
+    for (step, value) in (1..=20).zip(increments) {
+        assert_eq!(block_on(value).unwrap(), step * (step + 1) / 2);
+    }
+}
+
+#[test]
+fn calls_on_an_object_keep_their_order_in_the_pool() {
+    let cache = cache_with_lanes();
+
+    for counter in ["pool counter a", "pool counter b"] {
+        cache
+            .new_object(COUNTER, "(I)V", JavaArgs::I32(0), Some(counter.into()))
+            .unwrap();
+    }
+    // the calls on other objects are spread over the workers meanwhile
+    let others: Vec<_> = (0..10).map(|_| thread_name_async(&cache, 5)).collect();
+    // a slow increment is followed by a fast one, which a free worker would run first
+    let cache = &cache;
+    let increments: Vec<_> = ["pool counter a", "pool counter b"]
+        .into_iter()
+        .flat_map(|counter| {
+            (1..=10).map(move |step| increment_after_async(cache, counter, step, 30 * (step % 2)))
+        })
+        .collect();
+    for (i, value) in increments.into_iter().enumerate() {
+        let step = i as i32 % 10 + 1;
+        assert_eq!(block_on(value).unwrap(), step * (step + 1) / 2);
+    }
+    for name in others {
+        assert!(block_on(name).unwrap().starts_with("jvm-"));
+    }
+}
+
+#[test]
+fn calls_on_an_object_keep_their_order_across_lanes_and_pool() {
+    let cache = cache_with_lanes();
+    let ui = cache.on_lane("ui").unwrap();
+
+    cache
+        .new_object(
+            COUNTER,
+            "(I)V",
+            JavaArgs::I32(0),
+            Some("shared counter".into()),
+        )
+        .unwrap();
+    // the increments sent to the pool do not overtake the slow ones on the ui lane
+    let increments: Vec<_> = (1..=10)
+        .map(|step| {
+            if step % 2 == 1 {
+                increment_after_async(&ui, "shared counter", step, 30)
+            } else {
+                increment_after_async(&cache, "shared counter", step, 0)
+            }
+        })
+        .collect();
+    for (step, value) in (1..=10).zip(increments) {
+        assert_eq!(block_on(value).unwrap(), step * (step + 1) / 2);
+    }
+
+    // once its calls are answered, the object is free to take the route of the next one
+    let value = cache
+        .call_method::<i32>(
+            &ObjectId::from("shared counter"),
+            COUNTER,
+            "increment",
+            "(I)I",
+            JavaArgs::I32(0),
+            None,
+        )
+        .unwrap();
+    assert_eq!(value, 55);
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use common::{block_on, start_jvm, CALCULATOR};
+use jni_methods_cache::{JavaArgs, JniCache, JniCacheConfig};
+
+/// Own test binary : the cache of this process runs on a pool of three workers.
+fn pool() -> JniCache {
+    let config = JniCacheConfig {
+        pool_size: 3,
+        thread_name: "jvm-pool".to_string(),
+        stack_size: Some(4 * 1024 * 1024),
+        ..Default::default()
+    };
+    JniCache::init_with_config(start_jvm(), None, config, |builder| {
+        builder.add_standard_class_name("java/lang/String");
+    })
+    .expect("cannot build the cache")
+}
+
+fn thread_name(cache: &JniCache, millis: i32) -> String {
+    cache
+        .call_static_method::<String>(
+            CALCULATOR,
+            "threadName",
+            "(I)Ljava/lang/String;",
+            JavaArgs::I32(millis),
+            None,
+        )
+        .unwrap()
+}
+
+#[test]
+fn calls_are_spread_over_the_named_workers() {
+    let cache = pool();
+
+    // queued together, each one goes to the least loaded worker
+    let pending: Vec<_> = (0..3)
+        .map(|_| {
+            cache.call_static_method_async::<String>(
+                CALCULATOR,
+                "threadName",
+                "(I)Ljava/lang/String;",
+                JavaArgs::I32(300),
+                None,
+            )
+        })
+        .collect();
+    let names: HashSet<String> = pending
+        .into_iter()
+        .map(|future| block_on(future).unwrap())
+        .collect();
+    let expected: HashSet<String> = (0..3).map(|i| format!("jvm-pool-{}", i)).collect();
+    assert_eq!(names, expected);
+
+    // a slow call does not hold back the calls sent behind it
+    let slow = cache.call_static_method_async::<String>(
+        CALCULATOR,
+        "threadName",
+        "(I)Ljava/lang/String;",
+        JavaArgs::I32(1000),
+        None,
+    );
+    let hurried = cache.with_timeout(Duration::from_millis(500));
+    for _ in 0..5 {
+        assert!(thread_name(&hurried, 0).starts_with("jvm-pool-"));
+    }
+    assert!(block_on(slow).unwrap().starts_with("jvm-pool-"));
+
+    // the cache, and the methods resolved on first call, are shared by the workers
+    let sum = (0..6)
+        .map(|i| {
+            cache.call_static_method_async::<i32>(
+                CALCULATOR,
+                "add",
+                "(II)I",
+                JavaArgs::Array(vec![JavaArgs::I32(i), JavaArgs::I32(1)]),
+                None,
+            )
+        })
+        .map(|future| block_on(future).unwrap())
+        .sum::<i32>();
+    assert_eq!(sum, 21);
+    assert!(cache.is_built());
+}
@@ -0,0 +1,37 @@
+mod common;
+
+use common::{start_jvm, CALCULATOR};
+use jni_methods_cache::{JavaArgs, JniCache, JniCacheError, MethodType};
+
+/// Own test binary : the global cache of this process is only set by the second init.
+#[test]
+fn a_failed_build_is_returned_and_not_kept() {
+    let java_vm = start_jvm();
+    let vm_ptr = java_vm.get_java_vm_pointer();
+
+    let res = JniCache::init_with_vm(java_vm, None, |builder| {
+        builder.add_java_method(MethodType::Static, CALCULATOR, "missing", "()V");
+    });
+    assert!(matches!(res, Err(JniCacheError::BuildFailed(_))));
+    assert!(JniCache::global().is_none());
+
+    // the jvm outlives the failed cache, a new init builds another one
+    let cache = unsafe {
+        JniCache::init_with_raw_vm(vm_ptr, None, |builder| {
+            builder.add_java_method(MethodType::Static, CALCULATOR, "add", "(II)I");
+        })
+    }
+    .unwrap();
+    assert!(cache.is_built());
+    assert!(JniCache::global().is_some());
+    let res = cache
+        .call_static_method::<i32>(
+            CALCULATOR,
+            "add",
+            "(II)I",
+            JavaArgs::Array(vec![JavaArgs::I32(1), JavaArgs::I32(2)]),
+            None,
+        )
+        .unwrap();
+    assert_eq!(res, 3);
+}
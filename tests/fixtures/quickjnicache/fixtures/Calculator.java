@@ -0,0 +1,130 @@
+package quickjnicache.fixtures;
+
+import java.lang.ref.WeakReference;
+import java.util.ArrayList;
+import java.util.List;
+
+public class Calculator {
+    public static final int VERSION = 3;
+
+    public static String label = "calc";
+
+    public static long seed = 42L;
+
+    public static String nickname;
+
+    public static int add(int a, int b) {
+        return a + b;
+    }
+
+    public static long mix(boolean flag, byte b, char c, short s, long l, float f, double d) {
+        long res = b + c + s + l + (long) f + (long) d;
+        return flag ? res : -res;
+    }
+
+    public static int multiply(int a, int b) {
+        return a * b;
+    }
+
+    public static int negate(int value) {
+        return -value;
+    }
+
+    public static int slow(int millis) throws InterruptedException {
+        Thread.sleep(millis);
+        return millis;
+    }
+
+    public static String threadName(int millis) throws InterruptedException {
+        Thread.sleep(millis);
+        return Thread.currentThread().getName();
+    }
+
+    public static double half(double value) {
+        return value / 2.0;
+    }
+
+    public static boolean isEven(int value) {
+        return value % 2 == 0;
+    }
+
+    public static String greet(String name) {
+        return "Hello " + name;
+    }
+
+    private static final List<WeakReference<String>> tracked = new ArrayList<>();
+
+    public static synchronized String tracked(int i) {
+        String value = "tracked " + i;
+        tracked.add(new WeakReference<>(value));
+        return value;
+    }
+
+    /** Number of strings returned by tracked still reachable after a gc, a leaked local ref keeps its string. */
+    public static synchronized int liveTracked() throws InterruptedException {
+        for (int i = 0; i < 3; i++) {
+            System.gc();
+            Thread.sleep(10);
+        }
+        int live = 0;
+        for (WeakReference<String> ref : tracked) {
+            if (ref.get() != null) {
+                live++;
+            }
+        }
+        tracked.clear();
+        return live;
+    }
+
+    public static String find(String name) {
+        return name.isEmpty() ? null : name;
+    }
+
+    public static String kind(Object value) {
+        return value.getClass().getName();
+    }
+
+    public static Object token() {
+        return new Object();
+    }
+
+    public static void fail(String message) {
+        throw new IllegalStateException(message, new IllegalArgumentException("root cause"));
+    }
+
+    public static int[] ints() {
+        return new int[] {1, -2, 3};
+    }
+
+    public static double[] doubles() {
+        return new double[] {0.5, 1.5};
+    }
+
+    public static float[] floats() {
+        return new float[] {1.0f, 2.5f};
+    }
+
+    public static long[] longs() {
+        return new long[] {Long.MAX_VALUE, -1L};
+    }
+
+    public static byte[] bytes() {
+        return new byte[] {1, 2, (byte) 255};
+    }
+
+    public static boolean[] booleans() {
+        return new boolean[] {true, false};
+    }
+
+    public static short[] shorts() {
+        return new short[] {-3, 7};
+    }
+
+    public static char[] chars() {
+        return new char[] {'a', 'z'};
+    }
+
+    public static String[] strings() {
+        return new String[] {"one", null, "two"};
+    }
+}
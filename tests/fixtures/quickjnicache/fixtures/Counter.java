@@ -0,0 +1,31 @@
+package quickjnicache.fixtures;
+
+public class Counter {
+    private int value;
+
+    public Counter(int start) {
+        this.value = start;
+    }
+
+    public static Counter create(int start) {
+        return new Counter(start);
+    }
+
+    public static String describe(Counter counter) {
+        return "Counter(" + counter.value + ")";
+    }
+
+    public int increment(int step) {
+        value += step;
+        return value;
+    }
+
+    public int incrementAfter(int step, int millis) throws InterruptedException {
+        Thread.sleep(millis);
+        return increment(step);
+    }
+
+    public int get() {
+        return value;
+    }
+}
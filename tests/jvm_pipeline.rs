@@ -0,0 +1,767 @@
+mod common;
+
+use std::time::{Duration, Instant};
+
+use common::{block_on, fixture, CALCULATOR, COUNTER};
+use jni_methods_cache::{
+    FieldType, JavaArgs, JavaFieldRef, JniCache, JniCacheError, MethodType, ObjectId, ReturnedValue,
+};
+
+#[test]
+fn static_primitive_calls() {
+    let f = fixture();
+
+    assert_eq!(f.add.call(&f.cache, 2, 3).unwrap(), 5);
+    assert_eq!(f.half.call(&f.cache, 5.0).unwrap(), 2.5);
+    assert!(f.is_even.call(&f.cache, 4).unwrap());
+    assert!(!f.is_even.call(&f.cache, 3).unwrap());
+    assert_eq!(
+        f.mix
+            .call(&f.cache, false, 1, 'a' as u16, 2, 3, 4.0, 5.0)
+            .unwrap(),
+        -(1 + 97 + 2 + 3 + 4 + 5)
+    );
+}
+
+#[test]
+fn string_arguments_and_extraction() {
+    let f = fixture();
+
+    assert_eq!(
+        f.greet.call(&f.cache, "typed".into()).unwrap(),
+        "Hello typed"
+    );
+
+    let greeting: String = f
+        .cache
+        .call_static_method(
+            CALCULATOR,
+            "greet",
+            "(Ljava/lang/String;)Ljava/lang/String;",
+            JavaArgs::String("untyped".to_string()),
+            None,
+        )
+        .unwrap();
+    assert_eq!(greeting, "Hello untyped");
+}
+
+#[test]
+fn array_extraction() {
+    let f = fixture();
+
+    assert_eq!(f.ints.call(&f.cache).unwrap(), vec![1, -2, 3]);
+    assert_eq!(f.doubles.call(&f.cache).unwrap(), vec![0.5, 1.5]);
+    assert_eq!(f.floats.call(&f.cache).unwrap(), vec![1.0, 2.5]);
+    assert_eq!(f.longs.call(&f.cache).unwrap(), vec![i64::MAX, -1]);
+    assert_eq!(f.bytes.call(&f.cache).unwrap(), vec![1, 2, 255]);
+    assert_eq!(f.booleans.call(&f.cache).unwrap(), vec![true, false]);
+    assert_eq!(f.shorts.call(&f.cache).unwrap(), vec![-3, 7]);
+    assert_eq!(
+        f.chars.call(&f.cache).unwrap(),
+        vec!['a' as u16, 'z' as u16]
+    );
+    assert_eq!(
+        f.strings.call(&f.cache).unwrap(),
+        vec![Some("one".to_string()), None, Some("two".to_string())]
+    );
+}
+
+#[test]
+fn null_results_are_read_as_none() {
+    let f = fixture();
+
+    let find = f
+        .cache
+        .add_static_method::<(String,), Option<String>>(
+            CALCULATOR,
+            "find",
+            "(Ljava/lang/String;)Ljava/lang/String;",
+        )
+        .unwrap();
+    assert_eq!(find.call(&f.cache, String::new()).unwrap(), None);
+    assert_eq!(
+        find.call(&f.cache, "found".into()).unwrap(),
+        Some("found".to_string())
+    );
+    let nickname = f
+        .cache
+        .add_static_field::<Option<String>>(CALCULATOR, "nickname", "Ljava/lang/String;")
+        .unwrap();
+    assert_eq!(nickname.get(&f.cache).unwrap(), None);
+}
+
+#[test]
+fn object_store_round_trip() {
+    let f = fixture();
+
+    let counter: ObjectId = f
+        .cache
+        .call_static_method_by_handle(
+            f.create_counter,
+            JavaArgs::I32(10),
+            Some("round_trip_counter".into()),
+        )
+        .unwrap();
+    assert_eq!(counter, ObjectId::new("round_trip_counter"));
+
+    assert_eq!(f.increment.call(&f.cache, &counter, 5).unwrap(), 15);
+    assert_eq!(f.get.call(&f.cache, &counter).unwrap(), 15);
+    assert_eq!(
+        f.describe_counter.call(&f.cache, counter.clone()).unwrap(),
+        "Counter(15)"
+    );
+
+    let value: ReturnedValue = f
+        .cache
+        .call_method(&counter, COUNTER, "get", "()I", JavaArgs::None, None)
+        .unwrap();
+    assert!(matches!(value, ReturnedValue::I32(15)));
+}
+
+#[test]
+fn unregistered_method_is_reported() {
+    let f = fixture();
+
+    // the failed resolution is kept, the second call gets the same error
+    for _ in 0..2 {
+        let res =
+            f.cache
+                .call_static_method::<i32>(CALCULATOR, "sub", "(II)I", JavaArgs::None, None);
+        assert!(matches!(
+            res,
+            Err(JniCacheError::ResolutionFailed { ref member, .. }) if member.contains(".sub(")
+        ));
+    }
+}
+
+#[test]
+fn receiver_errors_are_reported() {
+    let f = fixture();
+
+    assert!(matches!(
+        f.increment.call(&f.cache, "no_such_counter", 1),
+        Err(JniCacheError::ObjectNotFound(ObjectId::Named(ref id))) if id == "no_such_counter"
+    ));
+
+    let token: ObjectId = f
+        .cache
+        .call_static_method_by_handle(f.token, JavaArgs::None, Some("plain_object".into()))
+        .unwrap();
+    assert!(matches!(
+        f.increment.call(&f.cache, &token, 1),
+        Err(JniCacheError::WrongReceiverClass { .. })
+    ));
+}
+
+#[test]
+fn argument_mismatch_is_reported() {
+    let f = fixture();
+
+    let arity =
+        f.cache
+            .call_static_method::<i32>(CALCULATOR, "add", "(II)I", JavaArgs::I32(1), None);
+    assert!(
+        matches!(arity, Err(JniCacheError::ArgumentMismatch { ref sig, .. }) if sig == "(II)I")
+    );
+
+    let types = f.cache.call_static_method::<i32>(
+        CALCULATOR,
+        "add",
+        "(II)I",
+        JavaArgs::Array(vec![JavaArgs::I32(1), JavaArgs::String("2".to_string())]),
+        None,
+    );
+    assert!(matches!(types, Err(JniCacheError::ArgumentMismatch { .. })));
+
+    // a stored object of another class never reaches java
+    let token: ObjectId = f
+        .cache
+        .call_static_method_by_handle(f.token, JavaArgs::None, None)
+        .unwrap();
+    let token = f.cache.guard_object(token);
+    assert!(matches!(
+        f.describe_counter.call(&f.cache, token.id().clone()),
+        Err(JniCacheError::ArgumentMismatch { ref sig, .. })
+            if sig == "(Lquickjnicache/fixtures/Counter;)Ljava/lang/String;"
+    ));
+    let counter = f
+        .cache
+        .new_object_by_handle(f.new_counter, JavaArgs::I32(7), None)
+        .unwrap();
+    let counter = f.cache.guard_object(counter);
+    assert_eq!(
+        f.describe_counter
+            .call(&f.cache, counter.id().clone())
+            .unwrap(),
+        "Counter(7)"
+    );
+}
+
+#[test]
+fn return_type_mismatch_is_reported() {
+    let f = fixture();
+
+    let res = f.cache.call_static_method::<String>(
+        CALCULATOR,
+        "add",
+        "(II)I",
+        JavaArgs::Array(vec![JavaArgs::I32(1), JavaArgs::I32(2)]),
+        None,
+    );
+    assert!(matches!(
+        res,
+        Err(JniCacheError::ReturnTypeMismatch { ref found, .. }) if found == "I32"
+    ));
+}
+
+#[test]
+fn java_exceptions_become_errors() {
+    let f = fixture();
+
+    match f.fail.call(&f.cache, "boom".into()) {
+        Err(JniCacheError::JavaException(throwable)) => {
+            assert_eq!(throwable.class_name, "java.lang.IllegalStateException");
+            assert_eq!(throwable.message.as_deref(), Some("boom"));
+            assert!(!throwable.stack_trace.is_empty());
+            let cause = throwable.cause.as_deref().expect("no cause captured");
+            assert_eq!(cause.class_name, "java.lang.IllegalArgumentException");
+            assert_eq!(cause.message.as_deref(), Some("root cause"));
+        }
+        other => panic!("expected a java exception, got {:?}", other),
+    }
+
+    // the executor survives the exception
+    assert_eq!(f.add.call(&f.cache, 1, 1).unwrap(), 2);
+}
+
+#[test]
+fn cache_handle_is_shared_between_threads() {
+    let f = fixture();
+
+    let global = JniCache::global().expect("no global cache");
+    assert!(global.is_built());
+
+    let add = f.add;
+    let sum = std::thread::spawn(move || add.call(&global, 20, 22))
+        .join()
+        .unwrap();
+    assert_eq!(sum.unwrap(), 42);
+}
+
+#[test]
+fn reused_object_id_replaces_the_stored_object() {
+    let f = fixture();
+
+    for start in [10, 20] {
+        let _: ObjectId = f
+            .cache
+            .call_static_method_by_handle(
+                f.create_counter,
+                JavaArgs::I32(start),
+                Some("reused_counter".into()),
+            )
+            .unwrap();
+    }
+
+    let ids = f.cache.object_ids().unwrap();
+    let reused = ObjectId::new("reused_counter");
+    assert_eq!(ids.iter().filter(|id| **id == reused).count(), 1);
+    assert_eq!(f.get.call(&f.cache, "reused_counter").unwrap(), 20);
+
+    let original = f.cache.find_object("reused_counter").unwrap().unwrap();
+    let replaced = f.cache.replace_object("copied_counter", original).unwrap();
+    assert!(replaced.is_none());
+    assert_eq!(f.increment.call(&f.cache, "copied_counter", 1).unwrap(), 21);
+    assert_eq!(f.get.call(&f.cache, "reused_counter").unwrap(), 21);
+}
+
+#[test]
+fn removed_objects_are_released() {
+    let f = fixture();
+
+    let counter: ObjectId = f
+        .cache
+        .call_static_method_by_handle(
+            f.create_counter,
+            JavaArgs::I32(1),
+            Some("removed_counter".into()),
+        )
+        .unwrap();
+    let count = f.cache.object_count().unwrap();
+
+    assert!(f.cache.remove_object(&counter).unwrap().is_some());
+    assert!(f.cache.remove_object(&counter).unwrap().is_none());
+    assert_eq!(f.cache.object_count().unwrap(), count - 1);
+    assert!(matches!(
+        f.get.call(&f.cache, &counter),
+        Err(JniCacheError::ObjectNotFound(_))
+    ));
+}
+
+#[test]
+fn stored_object_guard_removes_on_drop() {
+    let f = fixture();
+
+    let counter: ObjectId = f
+        .cache
+        .call_static_method_by_handle(
+            f.create_counter,
+            JavaArgs::I32(3),
+            Some("guarded_counter".into()),
+        )
+        .unwrap();
+    let guard = f.cache.guard_object(counter);
+    assert_eq!(f.increment.call(&f.cache, guard.id(), 2).unwrap(), 5);
+    assert_eq!(
+        f.describe_counter
+            .call(&f.cache, guard.id().clone())
+            .unwrap(),
+        "Counter(5)"
+    );
+
+    drop(guard);
+    assert!(f.cache.find_object("guarded_counter").unwrap().is_none());
+}
+
+#[test]
+fn unnamed_returned_objects_get_generated_ids() {
+    let f = fixture();
+
+    let first: ObjectId = f
+        .cache
+        .call_static_method_by_handle(f.create_counter, JavaArgs::I32(7), None)
+        .unwrap();
+    let second: ObjectId = f
+        .cache
+        .call_static_method_by_handle(f.create_counter, JavaArgs::I32(8), None)
+        .unwrap();
+    assert!(first.is_generated() && second.is_generated());
+    assert_ne!(first, second);
+
+    assert_eq!(f.get.call(&f.cache, &first).unwrap(), 7);
+    assert_eq!(f.get.call(&f.cache, &second).unwrap(), 8);
+
+    let _first = f.cache.guard_object(first);
+    let _second = f.cache.guard_object(second);
+}
+
+#[test]
+fn local_references_do_not_accumulate() {
+    let f = fixture();
+
+    // each call runs in its own local frame, nothing survives it but the stored objects
+    for i in 0..20_000 {
+        assert_eq!(f.greet.call(&f.cache, "loop".into()).unwrap(), "Hello loop");
+        assert_eq!(f.strings.call(&f.cache).unwrap().len(), 3);
+        if i % 1000 == 0 {
+            let counter: ObjectId = f
+                .cache
+                .call_static_method_by_handle(f.create_counter, JavaArgs::I32(i), None)
+                .unwrap();
+            let counter = f.cache.guard_object(counter);
+            assert_eq!(f.get.call(&f.cache, counter.id()).unwrap(), i);
+        }
+    }
+
+    // a local ref left behind would keep the string it points to from being collected
+    for i in 0..1000 {
+        let tracked: String = f
+            .cache
+            .call_static_method(
+                CALCULATOR,
+                "tracked",
+                "(I)Ljava/lang/String;",
+                JavaArgs::I32(i),
+                None,
+            )
+            .unwrap();
+        assert_eq!(tracked, format!("tracked {}", i));
+    }
+    let live: i32 = f
+        .cache
+        .call_static_method(CALCULATOR, "liveTracked", "()I", JavaArgs::None, None)
+        .unwrap();
+    assert_eq!(live, 0);
+}
+
+#[test]
+fn constructors_create_stored_objects() {
+    let f = fixture();
+
+    let counter = f
+        .cache
+        .new_object_by_handle(f.new_counter, JavaArgs::I32(40), None)
+        .unwrap();
+    assert!(counter.is_generated());
+    let counter = f.cache.guard_object(counter);
+    assert_eq!(f.increment.call(&f.cache, counter.id(), 2).unwrap(), 42);
+
+    let named = f
+        .cache
+        .new_object(
+            COUNTER,
+            "(I)V",
+            JavaArgs::I32(1),
+            Some("built_counter".into()),
+        )
+        .unwrap();
+    assert_eq!(named, ObjectId::new("built_counter"));
+    let named = f.cache.guard_object(named);
+    assert_eq!(
+        f.describe_counter
+            .call(&f.cache, named.id().clone())
+            .unwrap(),
+        "Counter(1)"
+    );
+
+    assert!(matches!(
+        f.cache.new_object(COUNTER, "()V", JavaArgs::None, None),
+        Err(JniCacheError::ResolutionFailed { .. })
+    ));
+    assert!(matches!(
+        f.cache
+            .new_object_by_handle(f.new_counter, JavaArgs::String("1".to_string()), None),
+        Err(JniCacheError::ArgumentMismatch { .. })
+    ));
+    assert!(matches!(
+        f.cache
+            .new_object_by_handle(f.create_counter, JavaArgs::I32(1), None),
+        Err(JniCacheError::InvalidMethodHandle(_))
+    ));
+}
+
+#[test]
+fn fields_are_read_and_written() {
+    let f = fixture();
+
+    assert_eq!(f.version.get(&f.cache).unwrap(), 3);
+    f.label.set(&f.cache, "renamed".to_string()).unwrap();
+    assert_eq!(f.label.get(&f.cache).unwrap(), "renamed");
+    let label: ReturnedValue = f
+        .cache
+        .get_static_field(
+            JavaFieldRef::by_name(CALCULATOR, "label", "Ljava/lang/String;"),
+            None,
+        )
+        .unwrap();
+    assert!(matches!(label, ReturnedValue::String(ref s) if s == "renamed"));
+    f.label.set(&f.cache, "calc".to_string()).unwrap();
+
+    let counter = f
+        .cache
+        .new_object_by_handle(f.new_counter, JavaArgs::I32(5), None)
+        .unwrap();
+    let counter = f.cache.guard_object(counter);
+    assert_eq!(f.counter_value.get(&f.cache, counter.id()).unwrap(), 5);
+    f.counter_value.set(&f.cache, counter.id(), 41).unwrap();
+    assert_eq!(f.increment.call(&f.cache, counter.id(), 1).unwrap(), 42);
+
+    assert!(matches!(
+        f.cache
+            .set_field(counter.id(), f.counter_value.handle(), JavaArgs::Long(1)),
+        Err(JniCacheError::ArgumentMismatch { .. })
+    ));
+    assert!(matches!(
+        f.cache
+            .get_static_field::<i32>(f.counter_value.handle(), None),
+        Err(JniCacheError::InvalidFieldHandle(_))
+    ));
+    assert!(matches!(
+        f.cache
+            .get_static_field::<i32>(JavaFieldRef::by_name(CALCULATOR, "missing", "I"), None),
+        Err(JniCacheError::ResolutionFailed { .. })
+    ));
+    assert!(matches!(
+        f.counter_value.get(&f.cache, "missing_counter"),
+        Err(JniCacheError::ObjectNotFound(_))
+    ));
+
+    let token: ObjectId = f
+        .cache
+        .call_static_method_by_handle(f.token, JavaArgs::None, None)
+        .unwrap();
+    let token = f.cache.guard_object(token);
+    assert!(matches!(
+        f.counter_value.get(&f.cache, token.id()),
+        Err(JniCacheError::WrongReceiverClass { .. })
+    ));
+    // a stored object of another class is never written to a field
+    assert!(matches!(
+        f.cache
+            .set_static_field(f.label.handle(), JavaArgs::JObject(token.id().clone())),
+        Err(JniCacheError::ArgumentMismatch { .. })
+    ));
+    assert_eq!(f.label.get(&f.cache).unwrap(), "calc");
+}
+
+#[test]
+fn bindings_are_registered_on_the_live_cache() {
+    let f = fixture();
+
+    let multiply = f
+        .cache
+        .add_static_method::<(i32, i32), i32>(CALCULATOR, "multiply", "(II)I")
+        .unwrap();
+    assert_eq!(multiply.call(&f.cache, 6, 7).unwrap(), 42);
+    let again = f
+        .cache
+        .add_java_method(MethodType::Static, CALCULATOR, "multiply", "(II)I")
+        .unwrap();
+    assert_eq!(again, multiply.handle());
+
+    let label = f
+        .cache
+        .add_static_field::<String>(CALCULATOR, "label", "Ljava/lang/String;")
+        .unwrap();
+    assert_eq!(label.handle(), f.label.handle());
+
+    let counter = f
+        .cache
+        .new_object_by_handle(f.new_counter, JavaArgs::I32(9), None)
+        .unwrap();
+    let counter = f.cache.guard_object(counter);
+    let value = f
+        .cache
+        .add_java_field(FieldType::NonStatic, COUNTER, "value", "I")
+        .unwrap();
+    assert_eq!(
+        f.cache.get_field::<i32>(counter.id(), value, None).unwrap(),
+        9
+    );
+
+    assert!(matches!(
+        f.cache
+            .add_java_method(MethodType::Static, CALCULATOR, "missing", "()V"),
+        Err(JniCacheError::ResolutionFailed { .. })
+    ));
+    assert!(matches!(
+        f.cache.add_java_method(
+            MethodType::Static,
+            "quickjnicache/fixtures/Missing",
+            "run",
+            "()V"
+        ),
+        Err(JniCacheError::ResolutionFailed { .. })
+    ));
+    assert!(matches!(
+        f.cache
+            .add_java_method(MethodType::Constructor, COUNTER, "create", "(I)V"),
+        Err(JniCacheError::ResolutionFailed { .. })
+    ));
+    assert!(matches!(
+        f.cache.add_standard_class_name("no/such/Klass"),
+        Err(JniCacheError::ResolutionFailed { .. })
+    ));
+    assert!(matches!(
+        f.cache.add_standard_class_name(COUNTER),
+        Err(JniCacheError::ResolutionFailed { .. })
+    ));
+    f.cache.add_standard_class_name("java/lang/String").unwrap();
+
+    // the cache still answers after the failed resolutions
+    assert_eq!(f.add.call(&f.cache, 1, 1).unwrap(), 2);
+}
+
+#[test]
+fn typed_bindings_are_checked_against_their_signature() {
+    let f = fixture();
+    // argument types, in order
+    assert!(f
+        .cache
+        .add_static_method::<(i64, i32), i32>(CALCULATOR, "multiply", "(II)I")
+        .is_err());
+    assert!(f
+        .cache
+        .add_static_method::<(String,), String>(
+            COUNTER,
+            "describe",
+            "(Lquickjnicache/fixtures/Counter;)Ljava/lang/String;",
+        )
+        .is_err());
+    // the element type of an array
+    assert!(f
+        .cache
+        .add_static_method::<(), Vec<f64>>(CALCULATOR, "ints", "()[I")
+        .is_err());
+    assert!(f
+        .cache
+        .add_static_method::<(), Vec<Option<String>>>(CALCULATOR, "ints", "()[I")
+        .is_err());
+    // a signature that does not parse
+    assert!(matches!(
+        f.cache
+            .add_static_method::<(i32,), i32>(CALCULATOR, "negate", "(I"),
+        Err(JniCacheError::ResolutionFailed { .. })
+    ));
+    assert!(f
+        .cache
+        .add_static_field::<Vec<u8>>(CALCULATOR, "label", "Ljava/lang/String;")
+        .is_err());
+
+    // a primitive is not boxed to go to an Object parameter
+    assert!(f
+        .cache
+        .add_static_method::<(i32,), String>(
+            CALCULATOR,
+            "kind",
+            "(Ljava/lang/Object;)Ljava/lang/String;"
+        )
+        .is_err());
+    let kind = f
+        .cache
+        .add_static_method::<(String,), String>(
+            CALCULATOR,
+            "kind",
+            "(Ljava/lang/Object;)Ljava/lang/String;",
+        )
+        .unwrap();
+    assert_eq!(
+        kind.call(&f.cache, "any".into()).unwrap(),
+        "java.lang.String"
+    );
+
+    // an ObjectId goes with any object or array
+    let ints = f
+        .cache
+        .add_static_method::<(), ObjectId>(CALCULATOR, "ints", "()[I")
+        .unwrap();
+    assert_eq!(ints.handle(), f.ints.handle());
+    let describe = f
+        .cache
+        .add_static_method::<(ObjectId,), String>(
+            COUNTER,
+            "describe",
+            "(Lquickjnicache/fixtures/Counter;)Ljava/lang/String;",
+        )
+        .unwrap();
+    assert_eq!(describe.handle(), f.describe_counter.handle());
+}
+
+#[test]
+fn named_bindings_are_resolved_on_first_call() {
+    let f = fixture();
+
+    for value in [3, 4] {
+        let res = f
+            .cache
+            .call_static_method::<i32>(CALCULATOR, "negate", "(I)I", JavaArgs::I32(value), None)
+            .unwrap();
+        assert_eq!(res, -value);
+    }
+    let seed: i64 = f
+        .cache
+        .get_static_field(JavaFieldRef::by_name(CALCULATOR, "seed", "J"), None)
+        .unwrap();
+    assert_eq!(seed, 42);
+
+    let counter = f
+        .cache
+        .new_object(COUNTER, "(I)V", JavaArgs::I32(2), None)
+        .unwrap();
+    let counter = f.cache.guard_object(counter);
+    let value: i32 = f
+        .cache
+        .call_method(counter.id(), COUNTER, "get", "()I", JavaArgs::None, None)
+        .unwrap();
+    assert_eq!(value, 2);
+
+    assert!(matches!(
+        f.cache
+            .call_static_method::<i32>(CALCULATOR, "negate", "(J)J", JavaArgs::Long(1), None),
+        Err(JniCacheError::ResolutionFailed { .. })
+    ));
+}
+
+#[test]
+fn async_calls_resolve_without_blocking() {
+    let f = fixture();
+
+    assert_eq!(block_on(f.add.call_async(&f.cache, 2, 3)).unwrap(), 5);
+
+    // queued at once, each future gets the answer of its own call
+    let futures: Vec<_> = (0..10)
+        .map(|i| f.add.call_async(&f.cache, i, 100))
+        .collect();
+    for (i, future) in futures.into_iter().enumerate().rev() {
+        assert_eq!(block_on(future).unwrap(), i as i32 + 100);
+    }
+
+    let greeting = f.cache.call_static_method_async::<String>(
+        CALCULATOR,
+        "greet",
+        "(Ljava/lang/String;)Ljava/lang/String;",
+        JavaArgs::String("async".to_string()),
+        None,
+    );
+    let greeting = std::thread::spawn(move || block_on(greeting))
+        .join()
+        .unwrap();
+    assert_eq!(greeting.unwrap(), "Hello async");
+
+    let counter = f
+        .cache
+        .new_object_by_handle(f.new_counter, JavaArgs::I32(1), None)
+        .unwrap();
+    let counter = f.cache.guard_object(counter);
+    assert_eq!(
+        block_on(f.increment.call_async(&f.cache, counter.id(), 4)).unwrap(),
+        5
+    );
+    let value =
+        f.cache
+            .call_method_async::<i32>(counter.id(), COUNTER, "get", "()I", JavaArgs::None, None);
+    assert_eq!(block_on(value).unwrap(), 5);
+
+    assert!(matches!(
+        block_on(f.fail.call_async(&f.cache, "async failure".to_string())),
+        Err(JniCacheError::JavaException(_))
+    ));
+}
+
+#[test]
+fn calls_time_out_at_their_deadline() {
+    let f = fixture();
+    let hurried = f.cache.with_timeout(Duration::from_millis(50));
+
+    // started in time, the late result is discarded
+    let res =
+        hurried.call_static_method::<i32>(CALCULATOR, "slow", "(I)I", JavaArgs::I32(300), None);
+    assert!(matches!(res, Err(JniCacheError::Timeout)));
+
+    // awaited the same way, the future resolves at the deadline, not with the call
+    let sent = Instant::now();
+    let late = hurried.call_static_method_async::<i32>(
+        CALCULATOR,
+        "slow",
+        "(I)I",
+        JavaArgs::I32(300),
+        None,
+    );
+    assert!(matches!(block_on(late), Err(JniCacheError::Timeout)));
+    assert!(sent.elapsed() < Duration::from_millis(250));
+
+    // queued behind a slow call, dropped without running
+    let slow = f.cache.call_static_method_async::<i32>(
+        CALCULATOR,
+        "slow",
+        "(I)I",
+        JavaArgs::I32(300),
+        None,
+    );
+    let expired = f.add.call_async(&hurried, 1, 2);
+    assert!(matches!(block_on(expired), Err(JniCacheError::Timeout)));
+    assert_eq!(block_on(slow).unwrap(), 300);
+
+    let past = f.cache.with_deadline(Instant::now());
+    assert!(matches!(
+        f.add.call(&past, 1, 2),
+        Err(JniCacheError::Timeout)
+    ));
+    assert!(matches!(
+        past.add_java_method(MethodType::Static, CALCULATOR, "negate", "(I)I"),
+        Err(JniCacheError::Timeout)
+    ));
+
+    let patient = f.cache.with_timeout(Duration::from_secs(10));
+    assert_eq!(f.add.call(&patient, 1, 2).unwrap(), 3);
+}